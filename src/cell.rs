@@ -3,16 +3,19 @@ use crate::ecosystem::snake::{Snake, SnakeSpecies};
 use anyhow::{bail, Result};
 use cell_animal::CellAnimal;
 use cell_grass::CellGrass;
-use cell_water::CellWater;
+use cell_water::{CellWater, WaterFlowState};
+use serde::{Deserialize, Serialize};
 
 pub mod cell_animal;
 pub mod cell_grass;
 pub mod cell_water;
 
-#[derive(Debug)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct Cell {
     animal: CellAnimal,
     water: CellWater,
+    /// Whether `water` is currently flowing, purely for rendering; see [`WaterFlowState`]
+    water_flow: WaterFlowState,
     grass: CellGrass,
     height: u8,
 }
@@ -22,6 +25,7 @@ impl Cell {
         Cell {
             animal: CellAnimal::Empty,
             water: CellWater::Empty,
+            water_flow: WaterFlowState::Settled,
             grass: CellGrass::Empty,
             height,
         }
@@ -37,10 +41,12 @@ impl Cell {
                 SnakeSpecies::C => CellColor::SnakeC,
             },
             CellAnimal::Dead => CellColor::DeadMatter,
-            CellAnimal::Empty => match self.water {
-                CellWater::Shallow => CellColor::ShallowWater,
-                CellWater::Deep => CellColor::DeepWater,
-                CellWater::Empty => match self.grass {
+            CellAnimal::Empty => match (self.water, self.water_flow) {
+                (CellWater::Shallow, WaterFlowState::Settled) => CellColor::ShallowWater,
+                (CellWater::Shallow, WaterFlowState::Running) => CellColor::RunningShallowWater,
+                (CellWater::Deep, WaterFlowState::Settled) => CellColor::DeepWater,
+                (CellWater::Deep, WaterFlowState::Running) => CellColor::RunningDeepWater,
+                (CellWater::Empty, _) => match self.grass {
                     CellGrass::Dry => CellColor::DryGrass,
                     CellGrass::Low => CellColor::LowGrass,
                     CellGrass::High => CellColor::HighGrass,
@@ -55,6 +61,7 @@ impl Cell {
             CellColor::Empty => {
                 self.animal = CellAnimal::Empty;
                 self.water = CellWater::Empty;
+                self.water_flow = WaterFlowState::Settled;
                 self.grass = CellGrass::Empty;
             }
             CellColor::Insect => self.animal = CellAnimal::Insect(Default::default()),
@@ -68,7 +75,10 @@ impl Cell {
             CellColor::SnakeC => {
                 self.animal = CellAnimal::Snake(Box::new(Snake::new(SnakeSpecies::C)))
             }
-            CellColor::ShallowWater => self.water = CellWater::Shallow,
+            CellColor::ShallowWater => {
+                self.water = CellWater::Shallow;
+                self.water_flow = WaterFlowState::Settled;
+            }
             CellColor::LowGrass => self.grass = CellGrass::Low,
             _ => {
                 bail!("cannot set such color")
@@ -87,12 +97,18 @@ impl Cell {
     pub fn water(&self) -> CellWater {
         self.water
     }
+    pub fn water_flow(&self) -> WaterFlowState {
+        self.water_flow
+    }
     pub fn grass(&self) -> CellGrass {
         self.grass
     }
     pub fn set_water(&mut self, water: CellWater) {
         self.water = water;
     }
+    pub fn set_water_flow(&mut self, water_flow: WaterFlowState) {
+        self.water_flow = water_flow;
+    }
     pub fn set_grass(&mut self, grass: CellGrass) {
         self.grass = grass;
     }
@@ -1,8 +1,9 @@
 use crate::ecosystem::amphibian::Amphibian;
 use crate::ecosystem::insect::Insect;
 use crate::ecosystem::snake::Snake;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+#[derive(Debug, Deserialize, Serialize)]
 pub enum CellAnimal {
     Empty,
     Insect(Box<Insect>),
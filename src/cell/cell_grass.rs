@@ -1,4 +1,6 @@
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Deserialize, Serialize)]
 pub enum CellGrass {
     Empty,
     Dry,
@@ -10,4 +12,24 @@ impl CellGrass {
     pub fn is_empty(self) -> bool {
         self == CellGrass::Empty
     }
-}
\ No newline at end of file
+
+    /// The next level up, or `None` if already at `High`
+    pub fn denser(self) -> Option<Self> {
+        match self {
+            CellGrass::Empty => Some(CellGrass::Dry),
+            CellGrass::Dry => Some(CellGrass::Low),
+            CellGrass::Low => Some(CellGrass::High),
+            CellGrass::High => None,
+        }
+    }
+
+    /// The next level down, or `None` if already at `Empty`
+    pub fn sparser(self) -> Option<Self> {
+        match self {
+            CellGrass::Empty => None,
+            CellGrass::Dry => Some(CellGrass::Empty),
+            CellGrass::Low => Some(CellGrass::Dry),
+            CellGrass::High => Some(CellGrass::Low),
+        }
+    }
+}
@@ -1,4 +1,6 @@
-#[derive(Debug, Clone, Copy)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
 pub enum CellWater {
     Empty,
     Shallow,
@@ -6,6 +8,10 @@ pub enum CellWater {
 }
 
 impl CellWater {
+    pub fn is_empty(self) -> bool {
+        matches!(self, CellWater::Empty)
+    }
+
     pub fn drier(self) -> Option<Self> {
         match self {
             CellWater::Empty => None,
@@ -22,3 +28,18 @@ impl CellWater {
         }
     }
 }
+
+/// Whether a cell's water is actively moving or sitting at rest, independent of how much water it
+/// holds, so the renderer can tell a river or waterfall apart from a stagnant lake without the
+/// underlying [`CellWater`] volume changing at all.
+/// [`WaterFlowSystem`](crate::ecosystem::water_flow::WaterFlowSystem) sets it on each of its
+/// ticks: in incremental mode a cell is `Running` for the tick it gives or receives water and
+/// `Settled` otherwise; in proportional mode a cell is `Running` for the tick its quantized water
+/// level changes; equilibrium mode leaves every cell `Settled`. Painting a cell through
+/// [`Cell::with_color`](crate::cell::Cell::with_color) also resets it to `Settled`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default, Deserialize, Serialize)]
+pub enum WaterFlowState {
+    #[default]
+    Settled,
+    Running,
+}
@@ -14,6 +14,11 @@ pub enum CellColor {
     HighGrass,
     ShallowWater,
     DeepWater,
+    /// A brighter [`CellColor::ShallowWater`], for a cell whose water just gave or received flow
+    /// this tick; see [`WaterFlowState`](crate::cell::WaterFlowState)
+    RunningShallowWater,
+    /// A brighter [`CellColor::DeepWater`], see [`RunningShallowWater`](Self::RunningShallowWater)
+    RunningDeepWater,
     DeadMatter,
 }
 
@@ -31,6 +36,8 @@ impl CellColor {
         CellColor::HighGrass,
         CellColor::ShallowWater,
         CellColor::DeepWater,
+        CellColor::RunningShallowWater,
+        CellColor::RunningDeepWater,
         CellColor::DeadMatter,
     ];
 
@@ -63,6 +70,8 @@ impl CellColor {
             CellColor::HighGrass => [27, 116, 72],
             CellColor::ShallowWater => [47, 168, 232],
             CellColor::DeepWater => [9, 70, 99],
+            CellColor::RunningShallowWater => [130, 220, 255],
+            CellColor::RunningDeepWater => [19, 130, 184],
             CellColor::DeadMatter => [123, 123, 123],
         }
     }
@@ -80,7 +89,9 @@ impl CellColor {
             8 => Ok(CellColor::HighGrass),
             9 => Ok(CellColor::ShallowWater),
             10 => Ok(CellColor::DeepWater),
-            11 => Ok(CellColor::DeadMatter),
+            11 => Ok(CellColor::RunningShallowWater),
+            12 => Ok(CellColor::RunningDeepWater),
+            13 => Ok(CellColor::DeadMatter),
             _ => bail!("invalid color index: {}", index),
         }
     }
@@ -1,4 +1,7 @@
+use crate::ecosystem::water_flow::WaterFlowMode;
 use anyhow::Result;
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
 use serde::Deserialize;
 use std::fs;
 
@@ -9,6 +12,39 @@ pub struct Config {
     pub amphibian_mating_radius: usize,
     pub amphibian_starvation_delay_seconds: u64,
     pub amphibian_tick_seconds: u64,
+    /// The base cadence of the unified ecosystem scheduler. Every system's own `*_tick_seconds`
+    /// is converted into a whole number of these ticks via [`Self::ticks`], so it must divide
+    /// evenly into (or at least not exceed) the fastest system's cadence.
+    pub ecosystem_tick_seconds: u64,
+    /// Fraction of a trail-laying animal's per-step deposit that survives each step further back
+    /// along its remembered trail; see [`crate::ecosystem::simple_animal::SimpleAnimalSystem`].
+    pub food_pheromone_decay_per_step: f64,
+    /// Amount of food pheromone deposited at the spot where food was found, tapering by
+    /// [`Self::food_pheromone_decay_per_step`] for each step further back along the trail.
+    pub food_pheromone_deposit: f64,
+    pub food_pheromone_diffusion: f64,
+    pub food_pheromone_evaporation: f64,
+    /// How many of an animal's most recently visited points are remembered to lay a trail back
+    /// from a food find.
+    pub food_pheromone_trail_length: usize,
+    /// Per-tick probability that a grass cell that's far from water and not already `Empty`
+    /// dries out one level; see
+    /// [`crate::ecosystem::grass::GrassSystem`].
+    pub grass_dry_out_ratio: f64,
+    /// Minimum count, out of the 8 surrounding cells, of `Low`/`High` grass neighbors an
+    /// `Empty`/`Dry` cell needs to advance one level.
+    pub grass_growth_neighbor_threshold: usize,
+    /// A cell at or below this height is treated as close enough to water to grow grass even
+    /// with no water within [`Self::grass_water_radius`], standing in for low-lying moisture that
+    /// isn't otherwise modeled.
+    pub grass_height_threshold: u8,
+    /// Below this count of vegetated neighbors, `High` grass is considered isolated and degrades
+    /// one level instead of holding steady.
+    pub grass_isolation_neighbor_threshold: usize,
+    pub grass_tick_seconds: u64,
+    /// Radius searched around a cell for any water, to decide whether it counts as "close to
+    /// water" for growth and drying-out purposes.
+    pub grass_water_radius: usize,
     pub height_map: String,
     pub insect_destination_radius: usize,
     pub insect_eating_radius: usize,
@@ -16,8 +52,35 @@ pub struct Config {
     pub insect_starvation_delay_seconds: u64,
     pub insect_tick_seconds: u64,
     pub long_pooling_seconds: u64,
+    /// When set, and no `--load` snapshot is given, boot with a map procedurally built by
+    /// [`crate::map::Map::generate`] (parameterized by the other `map_generation_*` fields)
+    /// instead of [`crate::map::Map::new`]'s height-map image.
+    pub map_generate: bool,
+    pub map_generation_amphibian_count: usize,
+    /// Cells within this BFS distance of water get at least `CellGrass::Dry`; see
+    /// [`crate::map::MapGenerationParams::dry_grass_distance`].
+    pub map_generation_dry_grass_distance: usize,
+    /// Cells within this BFS distance of water get `CellGrass::High`; see
+    /// [`crate::map::MapGenerationParams::high_grass_distance`].
+    pub map_generation_high_grass_distance: usize,
+    pub map_generation_insect_count: usize,
+    /// Cells within this BFS distance of water get at least `CellGrass::Low`; see
+    /// [`crate::map::MapGenerationParams::low_grass_distance`].
+    pub map_generation_low_grass_distance: usize,
+    /// Number of cave-automata smoothing passes applied to the initial random water fill.
+    pub map_generation_smoothing_passes: usize,
+    /// Fraction of cells randomly seeded as water before smoothing.
+    pub map_generation_water_density: f64,
+    /// Out of the 8 surrounding neighbors, the minimum number that must be water for a cell to
+    /// become, or remain, water during smoothing.
+    pub map_generation_water_neighbor_threshold: usize,
     pub map_size: usize,
     pub port: u16,
+    pub scent_diffusion: f64,
+    pub scent_emission: f64,
+    pub scent_evaporation: f64,
+    pub scent_tick_seconds: u64,
+    pub seed: Option<u64>,
     pub snake_a_max_size: usize,
     pub snake_a_move_ratio: f64,
     pub snake_b_max_size: usize,
@@ -26,12 +89,21 @@ pub struct Config {
     pub snake_c_move_ratio: f64,
     pub snake_eating_radius: usize,
     pub snake_min_size: usize,
+    pub snake_move_collision_weight: f64,
+    pub snake_move_scent_weight: f64,
+    pub snake_move_space_weight: f64,
     pub snake_starvation_delay_seconds: u64,
     pub snake_tick_seconds: u64,
+    pub snapshot_max_keyframes: usize,
+    pub snapshot_tick_seconds: u64,
     pub water_evaporation_ratio: f64,
     pub water_evaporation_tick_seconds: u64,
     pub water_flow_max_radius: usize,
+    pub water_flow_mode: WaterFlowMode,
     pub water_flow_tick_seconds: u64,
+    /// Fraction of the height-plus-water surface-level difference transferred to a lower
+    /// neighbor each tick in [`crate::ecosystem::water_flow::WaterFlowMode::Proportional`] mode.
+    pub water_flow_transfer_fraction: f64,
     pub water_in_atmosphere_ratio: f64,
     pub water_max_cycle_seconds: u64,
     pub water_max_rain_radius: usize,
@@ -39,6 +111,8 @@ pub struct Config {
     pub water_rain_ratio: f64,
     pub water_rain_tick_seconds: u64,
     pub water_thickness: u8,
+    pub world_snapshot_path: String,
+    pub world_snapshot_tick_seconds: u64,
 }
 
 impl Config {
@@ -46,4 +120,22 @@ impl Config {
         let config: Config = serde_json::from_str(&fs::read_to_string("config/config.json")?)?;
         Ok(config)
     }
+
+    /// A `SmallRng` for one ecosystem system, seeded deterministically from [`Self::seed`] when
+    /// one is set, so a run can be replayed bit-for-bit. `salt` should be a constant distinct per
+    /// calling system, so that two systems sharing the same global seed still draw independent
+    /// sequences instead of identical ones.
+    pub fn system_rng(&self, salt: u64) -> SmallRng {
+        match self.seed {
+            Some(seed) => SmallRng::seed_from_u64(seed ^ salt),
+            None => SmallRng::from_entropy(),
+        }
+    }
+
+    /// Convert a per-system cadence, in seconds, into a whole number of
+    /// [`Self::ecosystem_tick_seconds`] scheduler ticks, rounding down but never going below 1 so
+    /// a system still advances at worst every tick.
+    pub fn ticks(&self, seconds: u64) -> usize {
+        (seconds / self.ecosystem_tick_seconds.max(1)).max(1) as usize
+    }
 }
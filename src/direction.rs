@@ -0,0 +1,53 @@
+use crate::point::Point;
+use serde::{Deserialize, Serialize};
+
+/// One of the 8 compass headings on the grid. Used as a restricted "ant-style" movement model:
+/// from a given heading, the only headings reachable in one step are itself, [`Self::cw`] or
+/// [`Self::ccw`] (a 45° turn either way), never a full reversal; see
+/// [`crate::ecosystem::simple_animal::SimpleAnimalKind::walk_candidates`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+pub enum Direction {
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+    North,
+    NorthEast,
+}
+
+impl Direction {
+    /// All 8 headings, in clockwise order starting at [`Self::East`], matching
+    /// [`Point::EIGHT_DIRECTIONS`] position for position
+    pub const ALL: [Direction; 8] = [
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+        Direction::North,
+        Direction::NorthEast,
+    ];
+
+    /// The grid offset this heading moves by
+    pub fn offset(self) -> Point {
+        Point::EIGHT_DIRECTIONS[self as usize]
+    }
+
+    /// Rotate 45° clockwise
+    pub fn cw(self) -> Self {
+        Self::ALL[(self as usize + 1) % Self::ALL.len()]
+    }
+
+    /// Rotate 45° counter-clockwise
+    pub fn ccw(self) -> Self {
+        Self::ALL[(self as usize + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    /// Rotate 180°
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + Self::ALL.len() / 2) % Self::ALL.len()]
+    }
+}
@@ -1,27 +1,65 @@
 pub mod amphibian;
+mod grass;
 pub mod insect;
+mod scent_field;
+mod scheduler;
 mod simple_animal;
 pub mod snake;
 mod water_cycle;
-mod water_flow;
+pub mod water_flow;
 
 use crate::config::Config;
 use crate::ecosystem::amphibian::AmphibianSystem;
+use crate::ecosystem::grass::GrassSystem;
 use crate::ecosystem::insect::InsectSystem;
+use crate::ecosystem::scent_field::ScentFieldSystem;
+use crate::ecosystem::scheduler::EcosystemSystem;
 use crate::ecosystem::snake::SnakeSystem;
 use crate::ecosystem::water_cycle::WaterCycleSystem;
 use crate::ecosystem::water_flow::WaterFlowSystem;
 use crate::map::Map;
 use crate::monitored_rwlock::MonitoredRwLock;
+use std::sync::atomic::{AtomicI32, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
-/// Continuously update the map, simulating all the living things
-pub fn spawn_ecosystem(config: Arc<Config>, map: Arc<MonitoredRwLock<Map>>) {
-    tokio::spawn(InsectSystem::new(&config, map.clone()).run());
-    tokio::spawn(AmphibianSystem::new(&config, map.clone()).run());
-    tokio::spawn(SnakeSystem::new(&config, map.clone()).run());
-    tokio::spawn(WaterCycleSystem::new(&config, map.clone()).run());
-    tokio::spawn(WaterFlowSystem::new(&config, map).run());
+/// Continuously update the map, simulating all the living things. `restored_atmosphere_water`
+/// overrides the water cycle's starting budget, e.g. with a value loaded from a snapshot, instead
+/// of the default derived from `water_in_atmosphere_ratio`. Returns a handle to that budget so
+/// callers (e.g. a periodic snapshot writer) can read its live value afterwards.
+pub fn spawn_ecosystem(
+    config: Arc<Config>,
+    map: Arc<MonitoredRwLock<Map>>,
+    restored_atmosphere_water: Option<i32>,
+) -> Arc<AtomicI32> {
+    let water_cycle = WaterCycleSystem::new(&config);
+    let atmosphere_water = water_cycle.atmosphere_water_handle();
+    if let Some(restored) = restored_atmosphere_water {
+        atmosphere_water.store(restored, Ordering::Relaxed);
+    }
+
+    // Fixed, deterministic order: insects, amphibians, the scent field they leave behind, snakes
+    // (which sample that field to hunt), grass, then the two water systems. All share a single
+    // write lock per tick instead of each independently contending for it, and running in the
+    // same order every time is what lets `Config::seed` reproduce a run bit for bit.
+    let systems: Vec<Box<dyn EcosystemSystem + Send>> = vec![
+        Box::new(InsectSystem::new(&config)),
+        Box::new(AmphibianSystem::new(&config)),
+        Box::new(ScentFieldSystem::new(&config)),
+        Box::new(SnakeSystem::new(&config)),
+        Box::new(GrassSystem::new(&config)),
+        Box::new(water_cycle),
+        Box::new(WaterFlowSystem::new(
+            &config,
+            &map.read(module_path!()),
+            atmosphere_water.clone(),
+        )),
+    ];
+    tokio::spawn(scheduler::run(
+        systems,
+        map,
+        Duration::from_secs(config.ecosystem_tick_seconds),
+    ));
 
     // tokio::spawn(async move {
     //     let mut map = map.write().unwrap();
@@ -50,4 +88,6 @@ pub fn spawn_ecosystem(config: Arc<Config>, map: Arc<MonitoredRwLock<Map>>) {
     //         time::sleep(Duration::from_millis(100)).await;
     //     }
     // });
+
+    atmosphere_water
 }
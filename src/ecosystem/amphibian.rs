@@ -1,40 +1,51 @@
 use crate::cell::cell_animal::CellAnimal;
 use crate::cell::Cell;
 use crate::config::Config;
-use crate::ecosystem::simple_animal::{
-    SimpleAnimal, SimpleAnimalKind, SimpleAnimalSystem, WalkCandidate,
-};
+use crate::ecosystem::scheduler::EcosystemSystem;
+use crate::ecosystem::simple_animal::{SimpleAnimal, SimpleAnimalKind, SimpleAnimalSystem};
 use crate::map::Map;
-use crate::point::Point;
-use std::array;
-use std::sync::{Arc, RwLock};
+use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
-#[derive(Debug, Default)]
+/// Arbitrary, only needs to differ from other systems' salts so their derived RNG streams
+/// diverge even when [`Config::seed`] is shared
+const RNG_SALT: u64 = 4;
+
+#[derive(Debug, Default, Deserialize, Serialize)]
 pub struct Amphibian(SimpleAnimal);
 
 #[derive(Debug)]
 pub struct AmphibianSystem(SimpleAnimalSystem<Amphibian>);
 
 impl AmphibianSystem {
-    pub fn new(config: &Config, map: Arc<RwLock<Map>>) -> Self {
+    pub fn new(config: &Config) -> Self {
         Self(SimpleAnimalSystem::new(
-            Duration::from_secs(config.amphibian_tick_seconds),
+            config.ticks(config.amphibian_tick_seconds),
             config.amphibian_eating_radius,
             config.amphibian_mating_radius,
             config.amphibian_destination_radius,
-            map,
+            Duration::from_secs(config.amphibian_starvation_delay_seconds),
+            config.food_pheromone_trail_length,
+            config.food_pheromone_deposit,
+            config.food_pheromone_decay_per_step,
+            config.food_pheromone_evaporation,
+            config.food_pheromone_diffusion,
+            config.system_rng(RNG_SALT),
         ))
     }
+}
+
+impl EcosystemSystem for AmphibianSystem {
+    fn interval_ticks(&self) -> usize {
+        self.0.interval_ticks()
+    }
 
-    pub async fn run(self) {
-        self.0.run().await
+    fn step(&mut self, map: &mut Map) -> bool {
+        self.0.step(map)
     }
 }
 
 impl SimpleAnimalKind for Amphibian {
-    type WalkCandidates = array::IntoIter<WalkCandidate, 4>;
-
     fn get(cell: &Cell) -> Option<&SimpleAnimal> {
         match cell.animal() {
             CellAnimal::Amphibian(amphibian) => Some(&amphibian.0),
@@ -49,16 +60,6 @@ impl SimpleAnimalKind for Amphibian {
         }
     }
 
-    fn walk_candidates(point: Point, direction: Point) -> Self::WalkCandidates {
-        [
-            WalkCandidate::new(point, direction.turn_right(), 1),
-            WalkCandidate::new(point, direction.turn_left(), 1),
-            WalkCandidate::new(point, direction, 1),
-            WalkCandidate::new(point, direction, 2),
-        ]
-        .into_iter()
-    }
-
     fn is_food_goal(cell: &Cell) -> bool {
         cell.animal().insect().is_some()
     }
@@ -0,0 +1,118 @@
+use crate::cell::cell_grass::CellGrass;
+use crate::config::Config;
+use crate::ecosystem::scheduler::EcosystemSystem;
+use crate::map::Map;
+use crate::point::Point;
+use rand::distributions::Bernoulli;
+use rand::prelude::{Distribution, SmallRng};
+
+/// Arbitrary, only needs to differ from other systems' salts so their derived RNG streams
+/// diverge even when [`Config::seed`] is shared
+const RNG_SALT: u64 = 5;
+
+/// Grows and withers grass over a neighborhood cellular-automata rule, so food regenerates
+/// dynamically instead of only shrinking as animals eat it or a user paints it in.
+#[derive(Debug)]
+pub struct GrassSystem {
+    interval_ticks: usize,
+    growth_neighbor_threshold: usize,
+    isolation_neighbor_threshold: usize,
+    water_radius: usize,
+    height_threshold: u8,
+    dry_out: Bernoulli,
+    rng: SmallRng,
+}
+
+impl GrassSystem {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            interval_ticks: config.ticks(config.grass_tick_seconds),
+            growth_neighbor_threshold: config.grass_growth_neighbor_threshold,
+            isolation_neighbor_threshold: config.grass_isolation_neighbor_threshold,
+            water_radius: config.grass_water_radius,
+            height_threshold: config.grass_height_threshold,
+            dry_out: Bernoulli::new(config.grass_dry_out_ratio)
+                .expect("grass_dry_out_ratio must be a valid probability"),
+            rng: config.system_rng(RNG_SALT),
+        }
+    }
+
+    fn is_near_water(&self, map: &Map, point: Point) -> bool {
+        point
+            .circle(self.water_radius, map.size())
+            .any(|candidate| !map.cells()[candidate].water().is_empty())
+    }
+
+    fn count_vegetated_neighbors(map: &Map, point: Point) -> usize {
+        Point::EIGHT_DIRECTIONS
+            .into_iter()
+            .filter(|&direction| {
+                map.cells()
+                    .get(point + direction)
+                    .map(|neighbor| matches!(neighbor.grass(), CellGrass::Low | CellGrass::High))
+                    .unwrap_or(false)
+            })
+            .count()
+    }
+
+    /// Compute every cell's next grass level from a read-only pass over `map`, so a cell's
+    /// neighbor count always reflects this tick's starting state regardless of iteration order,
+    /// then let [`Self::apply_changes`] write them all in a second pass
+    fn determine_changes(&mut self, map: &Map) -> Vec<(Point, CellGrass)> {
+        let mut changes = Vec::new();
+
+        for (ij, cell) in map.cells().indexed_iter() {
+            if !cell.water().is_empty() {
+                // Water cells have no grass of their own to grow or wither
+                continue;
+            }
+
+            let point = Point::new_ij(ij);
+            let vegetated_neighbors = Self::count_vegetated_neighbors(map, point);
+            let near_water = self.is_near_water(map, point);
+
+            let next = match cell.grass() {
+                CellGrass::Empty | CellGrass::Dry
+                    if vegetated_neighbors >= self.growth_neighbor_threshold
+                        && (near_water || cell.height() <= self.height_threshold) =>
+                {
+                    cell.grass().denser()
+                }
+                CellGrass::High if vegetated_neighbors < self.isolation_neighbor_threshold => {
+                    cell.grass().sparser()
+                }
+                grass if !grass.is_empty() && !near_water && self.dry_out.sample(&mut self.rng) => {
+                    grass.sparser()
+                }
+                _ => None,
+            };
+
+            if let Some(next) = next {
+                changes.push((point, next));
+            }
+        }
+
+        changes
+    }
+
+    fn apply_changes(map: &mut Map, changes: Vec<(Point, CellGrass)>) -> bool {
+        let changed = !changes.is_empty();
+
+        for (point, grass) in changes {
+            map.cells_mut()[point].set_grass(grass);
+        }
+
+        changed
+    }
+}
+
+impl EcosystemSystem for GrassSystem {
+    fn interval_ticks(&self) -> usize {
+        self.interval_ticks
+    }
+
+    fn step(&mut self, map: &mut Map) -> bool {
+        let changes = self.determine_changes(map);
+        Self::apply_changes(map, changes)
+    }
+}
@@ -1,41 +1,51 @@
 use crate::cell::cell_animal::CellAnimal;
 use crate::cell::Cell;
 use crate::config::Config;
-use crate::ecosystem::simple_animal::{
-    SimpleAnimal, SimpleAnimalKind, SimpleAnimalSystem, WalkCandidate,
-};
+use crate::ecosystem::scheduler::EcosystemSystem;
+use crate::ecosystem::simple_animal::{SimpleAnimal, SimpleAnimalKind, SimpleAnimalSystem};
 use crate::map::Map;
-use crate::point::Point;
-use std::array;
-use std::sync::{Arc, RwLock};
+use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
-#[derive(Debug, Default)]
+/// Arbitrary, only needs to differ from other systems' salts so their derived RNG streams
+/// diverge even when [`Config::seed`] is shared
+const RNG_SALT: u64 = 3;
+
+#[derive(Debug, Default, Deserialize, Serialize)]
 pub struct Insect(SimpleAnimal);
 
 #[derive(Debug)]
 pub struct InsectSystem(SimpleAnimalSystem<Insect>);
 
 impl InsectSystem {
-    pub fn new(config: &Config, map: Arc<RwLock<Map>>) -> Self {
+    pub fn new(config: &Config) -> Self {
         Self(SimpleAnimalSystem::new(
-            Duration::from_secs(config.insect_tick_seconds),
+            config.ticks(config.insect_tick_seconds),
             config.insect_eating_radius,
             config.insect_mating_radius,
             config.insect_destination_radius,
             Duration::from_secs(config.insect_starvation_delay_seconds),
-            map,
+            config.food_pheromone_trail_length,
+            config.food_pheromone_deposit,
+            config.food_pheromone_decay_per_step,
+            config.food_pheromone_evaporation,
+            config.food_pheromone_diffusion,
+            config.system_rng(RNG_SALT),
         ))
     }
+}
+
+impl EcosystemSystem for InsectSystem {
+    fn interval_ticks(&self) -> usize {
+        self.0.interval_ticks()
+    }
 
-    pub async fn run(self) {
-        self.0.run().await
+    fn step(&mut self, map: &mut Map) -> bool {
+        self.0.step(map)
     }
 }
 
 impl SimpleAnimalKind for Insect {
-    type WalkCandidates = array::IntoIter<WalkCandidate, 2>;
-
     fn get(cell: &Cell) -> Option<&SimpleAnimal> {
         cell.animal().insect().map(|insect| &insect.0)
     }
@@ -44,14 +54,6 @@ impl SimpleAnimalKind for Insect {
         cell.animal_mut().insect_mut().map(|insect| &mut insect.0)
     }
 
-    fn walk_candidates(point: Point, direction: Point) -> Self::WalkCandidates {
-        [
-            WalkCandidate::new(point, direction.turn_right(), 1),
-            WalkCandidate::new(point, direction.turn_left(), 1),
-        ]
-        .into_iter()
-    }
-
     fn is_food_goal(cell: &Cell) -> bool {
         cell.animal().is_dead()
     }
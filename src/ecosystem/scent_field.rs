@@ -0,0 +1,57 @@
+use crate::cell::cell_animal::CellAnimal;
+use crate::config::Config;
+use crate::ecosystem::scheduler::EcosystemSystem;
+use crate::map::Map;
+use crate::point::Point;
+
+/// Keeps the map's prey scent trail alive: deposits a fixed emission under every amphibian, then
+/// lets it diffuse and evaporate. Predators (see [`crate::ecosystem::snake`]) sample this field
+/// instead of scanning every prey on the map.
+#[derive(Debug)]
+pub struct ScentFieldSystem {
+    interval_ticks: usize,
+    emission: f64,
+    evaporation: f64,
+    diffusion: f64,
+}
+
+impl ScentFieldSystem {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            interval_ticks: config.ticks(config.scent_tick_seconds),
+            emission: config.scent_emission,
+            evaporation: config.scent_evaporation,
+            diffusion: config.scent_diffusion,
+        }
+    }
+
+    fn tick(&self, map: &mut Map) {
+        let deposits = map
+            .cells()
+            .indexed_iter()
+            .filter_map(|(ij, cell)| {
+                matches!(cell.animal(), CellAnimal::Amphibian(_)).then(|| Point::new_ij(ij))
+            })
+            .collect::<Vec<_>>();
+        for point in deposits {
+            map.deposit_scent(point, self.emission);
+        }
+
+        map.diffuse_scent(self.evaporation, self.diffusion);
+    }
+}
+
+impl EcosystemSystem for ScentFieldSystem {
+    fn interval_ticks(&self) -> usize {
+        self.interval_ticks
+    }
+
+    fn step(&mut self, map: &mut Map) -> bool {
+        self.tick(map);
+
+        // Scent is purely an AI signal and never affects cell color, so diffusing it here does
+        // not count towards the frontend-notify decision, exactly like the food pheromone field
+        // diffused inside `SimpleAnimalSystem::step`
+        false
+    }
+}
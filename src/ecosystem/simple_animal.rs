@@ -1,63 +1,102 @@
 use crate::cell::cell_animal::CellAnimal;
 use crate::cell::Cell;
+use crate::direction::Direction;
+use crate::ecosystem::scheduler::EcosystemSystem;
 use crate::map::Map;
-use crate::monitored_rwlock::MonitoredRwLock;
 use crate::point::Point;
 use itertools::Itertools;
-use rand::prelude::{IteratorRandom, SliceRandom, SmallRng};
-use rand::SeedableRng;
+use rand::distributions::WeightedIndex;
+use rand::prelude::{Distribution, IteratorRandom, SliceRandom, SmallRng};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
 use std::marker::PhantomData;
 use std::mem;
-use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::time;
 
-#[derive(Debug)]
+/// Upper bound of the uniform random term added to every candidate's weight in
+/// [`SimpleAnimalSystem::choose_by_pheromone`], so the choice is not decided by pheromone alone
+const PHEROMONE_EXPLORATION_NOISE: f64 = 0.1;
+
+/// Upper bound on how many states a single [`SimpleAnimalSystem::find_path`] search may expand,
+/// so a single tick stays bounded even when no path exists; see
+/// [`crate::ecosystem::snake::SnakeSystem`]'s identical constant for the same A* search.
+const MAX_PATHFINDING_EXPANSIONS: usize = 300;
+
+/// Relative weight of continuing straight ahead against turning 45° in
+/// [`SimpleAnimalSystem::turn_weight`], so a non-foraging animal's walk looks like a smooth
+/// trajectory rather than a jittery random zig-zag
+const STRAIGHT_WALK_WEIGHT: f64 = 4.0;
+/// Relative weight of a 45° turn in [`SimpleAnimalSystem::turn_weight`]; see
+/// [`STRAIGHT_WALK_WEIGHT`]
+const TURN_WALK_WEIGHT: f64 = 1.0;
+
+#[derive(Debug, Deserialize, Serialize)]
 pub struct SimpleAnimal {
     state: SimpleAnimalState,
-    direction: Point,
+    direction: Direction,
     destination: Option<Point>,
+    /// Not meaningful across a process restart, so a snapshot restore just treats every animal as
+    /// freshly fed rather than persisting a monotonic clock reading
+    #[serde(skip, default = "Instant::now")]
     last_feeding: Instant,
+    /// The most recently visited points, oldest first, bounded to
+    /// [`SimpleAnimalSystem`]'s configured trail length. Laid down as a decaying food pheromone
+    /// trail when this animal reaches food, so the rest of the population can follow it back.
+    #[serde(default)]
+    trail: VecDeque<Point>,
 }
 
 #[derive(Debug)]
 pub struct SimpleAnimalSystem<K> {
-    map: Arc<MonitoredRwLock<Map>>,
-    tick_sleep: Duration,
+    interval_ticks: usize,
     eating_radius: usize,
     mating_radius: usize,
     destination_radius: usize,
     rng: SmallRng,
     _phantom: PhantomData<K>,
     starvation_delay: Duration,
+    trail_length: usize,
+    food_pheromone_deposit: f64,
+    food_pheromone_decay_per_step: f64,
+    food_pheromone_evaporation: f64,
+    food_pheromone_diffusion: f64,
 }
 
 #[derive(Debug, Copy, Clone)]
 pub struct WalkCandidate {
     target: Point,
-    new_direction: Point,
+    new_direction: Direction,
 }
 
 impl WalkCandidate {
-    pub fn new(point: Point, direction: Point, scale: usize) -> Self {
+    pub fn new(point: Point, direction: Direction, scale: usize) -> Self {
         WalkCandidate {
-            target: point + direction * scale,
+            target: point + direction.offset() * scale,
             new_direction: direction,
         }
     }
 }
 
 pub trait SimpleAnimalKind {
-    type WalkCandidates: Iterator<Item = WalkCandidate>;
     fn get(cell: &Cell) -> Option<&SimpleAnimal>;
     fn get_mut(cell: &mut Cell) -> Option<&mut SimpleAnimal>;
-    fn walk_candidates(point: Point, direction: Point) -> Self::WalkCandidates;
+    /// The "ant-style" restricted move set: the forward cell plus the two adjacent diagonals,
+    /// never a reversal. Shared by every kind, so this has no reason to be overridden.
+    fn walk_candidates(point: Point, direction: Direction) -> [WalkCandidate; 3] {
+        [
+            WalkCandidate::new(point, direction, 1),
+            WalkCandidate::new(point, direction.cw(), 1),
+            WalkCandidate::new(point, direction.ccw(), 1),
+        ]
+    }
     fn is_food_goal(cell: &Cell) -> bool;
     fn is_mating_ground_goal(cell: &Cell) -> bool;
     fn build_cell(simple_animal: SimpleAnimal) -> CellAnimal;
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Deserialize, Serialize)]
 #[allow(clippy::enum_variant_names)]
 enum SimpleAnimalState {
     SearchFood,
@@ -76,51 +115,64 @@ enum Change {
     Starve,
 }
 
+impl SimpleAnimal {
+    /// Append `point` to the bounded trail of recently visited points, dropping the oldest entry
+    /// once `trail_length` is exceeded
+    fn remember_step(&mut self, point: Point, trail_length: usize) {
+        self.trail.push_back(point);
+        while self.trail.len() > trail_length {
+            self.trail.pop_front();
+        }
+    }
+}
+
 impl Default for SimpleAnimal {
     fn default() -> Self {
         SimpleAnimal {
             state: SimpleAnimalState::SearchFood,
-            direction: Point::X,
+            direction: Direction::East,
             destination: None,
             last_feeding: Instant::now(),
+            trail: VecDeque::new(),
         }
     }
 }
 
 impl<K: SimpleAnimalKind> SimpleAnimalSystem<K> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
-        tick_sleep: Duration,
+        interval_ticks: usize,
         eating_radius: usize,
         mating_radius: usize,
         destination_radius: usize,
         starvation_delay: Duration,
-        map: Arc<MonitoredRwLock<Map>>,
+        trail_length: usize,
+        food_pheromone_deposit: f64,
+        food_pheromone_decay_per_step: f64,
+        food_pheromone_evaporation: f64,
+        food_pheromone_diffusion: f64,
+        rng: SmallRng,
     ) -> Self {
         Self {
-            map,
-            tick_sleep,
+            interval_ticks,
             eating_radius,
             mating_radius,
             destination_radius,
             starvation_delay,
-            rng: SmallRng::from_entropy(),
+            trail_length,
+            food_pheromone_deposit,
+            food_pheromone_decay_per_step,
+            food_pheromone_evaporation,
+            food_pheromone_diffusion,
+            rng,
             _phantom: PhantomData,
         }
     }
 
-    pub async fn run(mut self) {
-        loop {
-            let changes = self.determine_changes();
-            self.apply_changes(changes);
-            time::sleep(self.tick_sleep).await;
-        }
-    }
-
     /// Determine what should change for each simple animal
-    fn determine_changes(&mut self) -> Vec<(Point, Change)> {
+    fn determine_changes(&mut self, map: &Map) -> Vec<(Point, Change)> {
         let now = Instant::now();
         let mut changes = Vec::new();
-        let map = self.map.read(module_path!());
 
         for (ij, cell) in map.cells().indexed_iter() {
             if let Some(simple_animal) = K::get(cell) {
@@ -129,20 +181,18 @@ impl<K: SimpleAnimalKind> SimpleAnimalSystem<K> {
                     .or_else(|| {
                         Self::determine_reached_goal(
                             &mut self.rng,
-                            &map,
+                            map,
                             point,
                             simple_animal,
                             self.eating_radius,
                             self.mating_radius,
                         )
                     })
-                    .or_else(|| {
-                        Self::determine_next_walk(&mut self.rng, &map, point, simple_animal)
-                    })
+                    .or_else(|| Self::determine_next_walk(&mut self.rng, map, point, simple_animal))
                     .unwrap_or_else(|| {
                         Self::determine_next_destination(
                             &mut self.rng,
-                            &map,
+                            map,
                             point,
                             simple_animal,
                             self.destination_radius,
@@ -175,7 +225,7 @@ impl<K: SimpleAnimalKind> SimpleAnimalSystem<K> {
     ) -> Option<Change> {
         match simple_animal.state {
             SimpleAnimalState::SearchFood => point
-                .circle(eating_radius, map.size())
+                .euclidean_circle(eating_radius, map.size())
                 .find(|&target| Self::check_food_goal(map, target))
                 .map(Change::Eat),
             SimpleAnimalState::SearchMatingGround => point
@@ -184,11 +234,11 @@ impl<K: SimpleAnimalKind> SimpleAnimalSystem<K> {
                 .find(|&target| Self::check_mating_ground_goal(map, target))
                 .map(|_| Change::SearchPartner),
             SimpleAnimalState::SearchPartner => point
-                .circle(mating_radius, map.size())
+                .euclidean_circle(mating_radius, map.size())
                 .find(|&target| Self::check_partner_goal(map, point, target))
                 .and_then(|partner| {
                     point
-                        .circle(mating_radius, map.size())
+                        .euclidean_circle(mating_radius, map.size())
                         .filter(|&target| Self::check_new_born(map, target))
                         .choose(rng)
                         .map(|new_born| Change::Mate { partner, new_born })
@@ -208,7 +258,12 @@ impl<K: SimpleAnimalKind> SimpleAnimalSystem<K> {
             return None;
         }
 
+        if let Some(candidate) = Self::find_path(map, point, simple_animal.direction, destination) {
+            return Some(Change::MoveTo(candidate));
+        }
+
         let closest_candidates = K::walk_candidates(point, simple_animal.direction)
+            .into_iter()
             .filter(|candidate| {
                 map.cells()
                     .get(candidate.target)
@@ -217,7 +272,135 @@ impl<K: SimpleAnimalKind> SimpleAnimalSystem<K> {
             })
             .min_set_by_key(|candidate| candidate.target.distance(destination));
 
-        closest_candidates.choose(rng).copied().map(Change::MoveTo)
+        let chosen = if simple_animal.state == SimpleAnimalState::SearchFood {
+            Self::choose_by_pheromone(rng, map, &closest_candidates, |candidate| candidate.target)
+        } else {
+            closest_candidates
+                .choose_weighted(rng, |candidate| {
+                    Self::turn_weight(simple_animal.direction, candidate.new_direction)
+                })
+                .ok()
+                .copied()
+        };
+
+        chosen.map(Change::MoveTo)
+    }
+
+    /// Relative likelihood of picking a candidate that keeps heading `direction`, against one
+    /// that turns 45° to `candidate_direction`, so a non-foraging walk favors smooth trajectories
+    /// over jittery zig-zags. The restricted move set never offers a 90° turn or a reversal in
+    /// the first place, so those costs don't need representing here.
+    fn turn_weight(direction: Direction, candidate_direction: Direction) -> f64 {
+        if candidate_direction == direction {
+            STRAIGHT_WALK_WEIGHT
+        } else {
+            TURN_WALK_WEIGHT
+        }
+    }
+
+    /// Search a walk-state graph from `start` (facing `start_direction`) to `goal` with A*,
+    /// returning only the first step's [`WalkCandidate`]. States are `(Point, Direction)` pairs of
+    /// position and facing, not just position, because [`SimpleAnimalKind::walk_candidates`] only
+    /// offers neighbors reachable from the current facing (never a reversal), so the facing is
+    /// part of what defines a reachable state. This mirrors
+    /// [`crate::ecosystem::snake::SnakeSystem::find_path`]'s A* closely: cost per step is uniform
+    /// (1). Unlike that search, the heuristic is the Chebyshev distance rather than
+    /// [`Point::distance`], since a diagonal step covers one cell on both axes at once, which keeps
+    /// it admissible; and `goal` is never granted automatic passability, since reaching a
+    /// destination here never requires stepping onto an occupied cell.
+    fn find_path(
+        map: &Map,
+        start: Point,
+        start_direction: Direction,
+        goal: Point,
+    ) -> Option<WalkCandidate> {
+        let mut open = BinaryHeap::new();
+        let mut best_cost = HashMap::new();
+        let mut came_from: HashMap<(Point, Direction), ((Point, Direction), WalkCandidate)> =
+            HashMap::new();
+        let mut expansions = 0;
+        let heuristic = |point: Point| point.x.abs_diff(goal.x).max(point.y.abs_diff(goal.y));
+
+        best_cost.insert((start, start_direction), 0usize);
+        open.push(Reverse((
+            heuristic(start),
+            0usize,
+            start,
+            start_direction,
+        )));
+
+        while let Some(Reverse((_, cost, point, direction))) = open.pop() {
+            if point == goal {
+                let mut state = (point, direction);
+                while let Some(&(prev_state, candidate)) = came_from.get(&state) {
+                    if prev_state == (start, start_direction) {
+                        return Some(candidate);
+                    }
+                    state = prev_state;
+                }
+                return None;
+            }
+
+            if cost > *best_cost.get(&(point, direction)).unwrap_or(&usize::MAX) {
+                // A cheaper path to this state was already expanded
+                continue;
+            }
+
+            expansions += 1;
+            if expansions > MAX_PATHFINDING_EXPANSIONS {
+                return None;
+            }
+
+            for candidate in K::walk_candidates(point, direction) {
+                let passable = map
+                    .cells()
+                    .get(candidate.target)
+                    .map(|cell| cell.animal().is_empty())
+                    .unwrap_or(false);
+                if !passable {
+                    continue;
+                }
+
+                let neighbor = (candidate.target, candidate.new_direction);
+                let next_cost = cost + 1;
+                if next_cost < *best_cost.get(&neighbor).unwrap_or(&usize::MAX) {
+                    best_cost.insert(neighbor, next_cost);
+                    came_from.insert(neighbor, ((point, direction), candidate));
+                    open.push(Reverse((
+                        next_cost + heuristic(candidate.target),
+                        next_cost,
+                        candidate.target,
+                        candidate.new_direction,
+                    )));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Pick one of `candidates` with a probability that grows with the food pheromone sitting on
+    /// it (a softmax over the pheromone concentration), plus a random term of up to
+    /// [`PHEROMONE_EXPLORATION_NOISE`]. On a trail-less patch of map every candidate weighs about
+    /// the same. This is what turns foraging into trail-following once pheromone exists,
+    /// without hard-coding food locations.
+    fn choose_by_pheromone<T: Copy>(
+        rng: &mut SmallRng,
+        map: &Map,
+        candidates: &[T],
+        target_of: impl Fn(T) -> Point,
+    ) -> Option<T> {
+        let weights = candidates.iter().map(|&candidate| {
+            let pheromone = map
+                .food_pheromone()
+                .get(target_of(candidate))
+                .copied()
+                .unwrap_or(0.0);
+            pheromone.exp() + rng.gen::<f64>() * PHEROMONE_EXPLORATION_NOISE
+        });
+
+        let distribution = WeightedIndex::new(weights).ok()?;
+        Some(candidates[distribution.sample(rng)])
     }
 
     /// Determine a next walking destination, trying to achieve this state's goal
@@ -247,8 +430,20 @@ impl<K: SimpleAnimalKind> SimpleAnimalSystem<K> {
         }
 
         match simple_animal.state {
+            // If no direct goal-fulfilling destination was found, walk towards whichever far
+            // point carries the strongest trail of food pheromone, falling back to picking
+            // uniformly at random when the whole circumference is trail-less
+            SimpleAnimalState::SearchFood => {
+                let candidates = point
+                    .circumference(destination_radius, map.size())
+                    .collect_vec();
+                let destination = Self::choose_by_pheromone(rng, map, &candidates, |target| target)
+                    .expect("must have at least one point");
+
+                Change::SetDestination(destination)
+            }
             // If no direct goal-fulfilling destination was found, walk to a random far point
-            SimpleAnimalState::SearchFood | SimpleAnimalState::SearchMatingGround => {
+            SimpleAnimalState::SearchMatingGround => {
                 let destination = point
                     .circumference(destination_radius, map.size())
                     .choose(rng)
@@ -299,10 +494,20 @@ impl<K: SimpleAnimalKind> SimpleAnimalSystem<K> {
             .unwrap_or(false)
     }
 
+    /// Lay a food pheromone trail from where the food was found (`trail[0]`) back along the
+    /// animal's recent history, strongest at the find and decaying by
+    /// [`Self::food_pheromone_decay_per_step`] for each step further back
+    fn deposit_food_pheromone(&self, map: &mut Map, trail: &[Point]) {
+        for (distance, &trail_point) in trail.iter().enumerate() {
+            let deposit = self.food_pheromone_deposit
+                * self.food_pheromone_decay_per_step.powi(distance as i32);
+            map.deposit_food_pheromone(trail_point, deposit);
+        }
+    }
+
     /// Apply the changes, taking care to re-check if the necessary conditions still hold
-    fn apply_changes(&mut self, changes: Vec<(Point, Change)>) {
+    fn apply_changes(&mut self, map: &mut Map, changes: Vec<(Point, Change)>) -> bool {
         let now = Instant::now();
-        let mut map = self.map.write(module_path!());
         let mut changed_map = false;
 
         for (point, change) in changes {
@@ -350,6 +555,7 @@ impl<K: SimpleAnimalKind> SimpleAnimalSystem<K> {
                     }
                 }
                 Change::Eat(target) => {
+                    let mut trail = None;
                     let (animal, food) = map.two_cells_mut(point, target);
 
                     if let (Some(simple_animal), true) = (K::get_mut(animal), K::is_food_goal(food))
@@ -358,10 +564,28 @@ impl<K: SimpleAnimalKind> SimpleAnimalSystem<K> {
                             simple_animal.state = SimpleAnimalState::SearchMatingGround;
                             simple_animal.destination = None;
                             simple_animal.last_feeding = now;
+                            // The newest trail entry is the animal's current position, i.e.
+                            // `point` itself, which is already the first entry here
+                            trail = Some(
+                                std::iter::once(point)
+                                    .chain(
+                                        simple_animal
+                                            .trail
+                                            .iter()
+                                            .rev()
+                                            .copied()
+                                            .skip_while(|&visited| visited == point),
+                                    )
+                                    .collect_vec(),
+                            );
                             *food.animal_mut() = CellAnimal::Empty;
                             changed_map = true;
                         }
                     }
+
+                    if let Some(trail) = trail {
+                        self.deposit_food_pheromone(map, &trail);
+                    }
                 }
                 Change::Mate { partner, new_born } => {
                     let (partner_1, partner_2, new_born) =
@@ -393,13 +617,34 @@ impl<K: SimpleAnimalKind> SimpleAnimalSystem<K> {
                         from_insect.direction = candidate.new_direction;
                         mem::swap(from.animal_mut(), to.animal_mut());
                         changed_map = true;
+
+                        if let Some(moved) = K::get_mut(to) {
+                            moved.remember_step(candidate.target, self.trail_length);
+                        }
                     }
                 }
             }
         }
 
-        if changed_map {
-            map.notify_update();
-        }
+        // Pheromone is purely an AI signal and never affects cell color, so diffusing it here
+        // does not count towards `changed_map`/[`Self::step`]'s notify-the-frontend decision,
+        // exactly like `ScentFieldSystem`'s scent field
+        map.diffuse_food_pheromone(
+            self.food_pheromone_evaporation,
+            self.food_pheromone_diffusion,
+        );
+
+        changed_map
+    }
+}
+
+impl<K: SimpleAnimalKind> EcosystemSystem for SimpleAnimalSystem<K> {
+    fn interval_ticks(&self) -> usize {
+        self.interval_ticks
+    }
+
+    fn step(&mut self, map: &mut Map) -> bool {
+        let changes = self.determine_changes(map);
+        self.apply_changes(map, changes)
     }
 }
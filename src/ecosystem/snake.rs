@@ -1,41 +1,56 @@
 use crate::cell::cell_animal::CellAnimal;
 use crate::config::Config;
+use crate::ecosystem::scheduler::EcosystemSystem;
 use crate::map::Map;
-use crate::monitored_rwlock::MonitoredRwLock;
 use crate::point::Point;
 use itertools::Itertools;
 use rand::prelude::{IteratorRandom, SliceRandom, SmallRng};
-use rand::{Rng, SeedableRng};
-use std::collections::{HashMap, HashSet, VecDeque};
-use std::sync::Arc;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 use std::time::{Duration, Instant};
-use tokio::time;
 
-#[derive(Debug)]
+/// Arbitrary, only needs to differ from other systems' salts so their derived RNG streams
+/// diverge even when [`Config::seed`] is shared
+const RNG_SALT: u64 = 2;
+
+/// Upper bound on how many nodes a single [`SnakeSystem::find_path`] search may expand, so a
+/// single tick stays bounded even when no path exists
+const MAX_PATHFINDING_EXPANSIONS: usize = 300;
+
+/// Upper bound on how many cells a single [`SnakeSystem::flood_fill_area`] call will count, so
+/// open areas don't make the space-awareness check itself expensive
+const MAX_FLOOD_FILL_CELLS: usize = 128;
+
+#[derive(Debug, Deserialize, Serialize)]
 pub struct Snake {
     species: SnakeSpecies,
     segment: Option<SnakeSegment>,
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
 pub enum SnakeSpecies {
     A,
     B,
     C,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
 struct SnakeSegment {
     kind: SnakeSegmentKind,
     next_segment: Option<Point>,
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Deserialize, Serialize)]
 enum SnakeSegmentKind {
     /// The head is a special segment because it's used to actually identify each individual.
     /// Without this distinction, if individuals came too close to each other and had their segments
     /// well aligned, they would be re-interpreted as one.
     Head {
+        /// Not meaningful across a process restart, so a snapshot restore just treats every head
+        /// as freshly fed rather than persisting a monotonic clock reading
+        #[serde(skip, default = "Instant::now")]
         last_feeding: Instant,
     },
     Body,
@@ -43,7 +58,6 @@ enum SnakeSegmentKind {
 
 #[derive(Debug)]
 pub struct SnakeSystem {
-    map: Arc<MonitoredRwLock<Map>>,
     rng: SmallRng,
     a_max_size: usize,
     a_move_ratio: f64,
@@ -53,8 +67,11 @@ pub struct SnakeSystem {
     c_move_ratio: f64,
     min_size: usize,
     eating_radius: usize,
+    move_scent_weight: f64,
+    move_space_weight: f64,
+    move_collision_weight: f64,
     starvation_delay: Duration,
-    tick_sleep: Duration,
+    interval_ticks: usize,
 }
 
 #[derive(Debug)]
@@ -71,20 +88,42 @@ enum Change {
         food: Point,
     },
     Death(Point),
+    /// An attacker killed a different-species snake, either by striking one of its body segments
+    /// or by winning a head-to-head duel. `winner_head` is `None` when a head-to-head duel ends
+    /// in a draw, in which case nobody grows and every point in `loser_points` dies.
+    Combat {
+        winner_head: Option<Point>,
+        loser_points: Vec<Point>,
+    },
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 struct SnakeSegmentSet {
     heads: HashMap<Point, SnakeSegment>,
     bodies: HashMap<Point, SnakeSegment>,
     spare_parts: HashSet<Point>,
 }
 
+/// A snake that rolled its move this tick without finding an immediate fight or an adjacent meal,
+/// and so is awaiting a scored, conflict-aware target from [`SnakeSystem::determine_movements`].
+#[derive(Debug)]
+struct PendingMove {
+    points: Vec<Point>,
+}
+
+/// What an individual snake does this tick, as decided by
+/// [`SnakeSystem::determine_immediate_decision`]: either its fate is already settled, or it still
+/// needs a movement target from the joint scoring pass.
+#[derive(Debug)]
+enum SnakeDecision {
+    Change(Change),
+    Pending(PendingMove),
+}
+
 impl SnakeSystem {
-    pub fn new(config: &Config, map: Arc<MonitoredRwLock<Map>>) -> Self {
+    pub fn new(config: &Config) -> Self {
         Self {
-            map,
-            rng: SmallRng::from_entropy(),
+            rng: config.system_rng(RNG_SALT),
             a_max_size: config.snake_a_max_size,
             a_move_ratio: config.snake_a_move_ratio,
             b_max_size: config.snake_b_max_size,
@@ -93,23 +132,16 @@ impl SnakeSystem {
             c_move_ratio: config.snake_c_move_ratio,
             min_size: config.snake_min_size,
             eating_radius: config.snake_eating_radius,
+            move_scent_weight: config.snake_move_scent_weight,
+            move_space_weight: config.snake_move_space_weight,
+            move_collision_weight: config.snake_move_collision_weight,
             starvation_delay: Duration::from_secs(config.snake_starvation_delay_seconds),
-            tick_sleep: Duration::from_secs(config.snake_tick_seconds),
-        }
-    }
-
-    pub async fn run(mut self) {
-        loop {
-            let changes = self.determine_changes();
-            self.apply_changes(changes);
-            time::sleep(self.tick_sleep).await;
+            interval_ticks: config.ticks(config.snake_tick_seconds),
         }
     }
 
-    fn determine_changes(&mut self) -> Vec<Change> {
+    fn determine_changes(&mut self, map: &Map) -> Vec<Change> {
         let mut changes = Vec::new();
-        let map = self.map.clone();
-        let map = map.read(module_path!());
 
         // Index the snakes by their species, kind and position.
         // Also index amphibians, their preys.
@@ -142,15 +174,49 @@ impl SnakeSystem {
             }
         }
 
-        // Determine the changes for each species
-        for (species, segment_set) in snakes {
-            changes.extend(self.determine_species_changes(
+        // Index every snake segment (of any species) by position, plus a reverse lookup from any
+        // of its points back to its head, so combat detection can recognize an enemy segment and
+        // resolve it to the whole snake without re-scanning the map per candidate
+        let mut all_segments: HashMap<Point, SnakeSpecies> = HashMap::default();
+        let mut owning_head: HashMap<Point, Point> = HashMap::default();
+        for (&species, segment_set) in snakes.iter().sorted_by_key(|&(&species, _)| species) {
+            for (&head_point, head_segment) in
+                segment_set.heads.iter().sorted_by_key(|&(&point, _)| point)
+            {
+                let points = Self::extract_snake_chain(
+                    self.max_size(species),
+                    head_point,
+                    head_segment.next_segment,
+                    &segment_set.bodies,
+                );
+                for point in points {
+                    all_segments.insert(point, species);
+                    owning_head.insert(point, head_point);
+                }
+            }
+        }
+        let all_snakes = snakes.clone();
+
+        // Determine the changes for each species. Movement is handled separately below: each
+        // species only contributes candidate movers, which are then scored and assigned jointly
+        // across every species in one pass. Species are visited in a fixed order (rather than
+        // the `HashMap`'s randomized one) so RNG draws stay reproducible under a fixed
+        // `Config::seed`.
+        let mut movers = Vec::new();
+        for (species, segment_set) in snakes.into_iter().sorted_by_key(|&(species, _)| species) {
+            let (species_changes, species_movers) = self.determine_species_changes(
                 &map,
                 species,
                 segment_set,
                 &mut uneaten_preys,
-            ));
+                &all_segments,
+                &owning_head,
+                &all_snakes,
+            );
+            changes.extend(species_changes);
+            movers.extend(species_movers);
         }
+        changes.extend(self.determine_movements(&map, movers));
 
         changes
     }
@@ -161,13 +227,26 @@ impl SnakeSystem {
         species: SnakeSpecies,
         mut segment_set: SnakeSegmentSet,
         uneaten_preys: &mut HashSet<Point>,
-    ) -> Vec<Change> {
+        all_segments: &HashMap<Point, SnakeSpecies>,
+        owning_head: &HashMap<Point, Point>,
+        all_snakes: &HashMap<SnakeSpecies, SnakeSegmentSet>,
+    ) -> (Vec<Change>, Vec<PendingMove>) {
         let max_size = self.max_size(species);
         let mut changes = Vec::new();
+        let mut movers = Vec::new();
         let now = Instant::now();
 
-        // Determine where to move to each existing snake
-        for (point, head) in segment_set.heads {
+        // Determine where to move to each existing snake, visiting heads in a fixed order
+        // (rather than the `HashMap`'s randomized one) so RNG draws stay reproducible under a
+        // fixed `Config::seed`
+        let mut heads = segment_set
+            .heads
+            .iter()
+            .map(|(&point, &head)| (point, head))
+            .collect_vec();
+        heads.sort_by_key(|&(point, _)| point);
+
+        for (point, head) in heads {
             let snake_points =
                 self.extract_snake(max_size, point, head.next_segment, &mut segment_set.bodies);
 
@@ -177,20 +256,24 @@ impl SnakeSystem {
                     changes.push(Change::Death(point));
                 }
                 Some(snake_points) => {
-                    let change =
-                        self.determine_starvation(now, head, &snake_points)
-                            .or_else(|| {
-                                self.determine_next_movement(
-                                    map,
-                                    species,
-                                    snake_points,
-                                    uneaten_preys,
-                                    max_size,
-                                )
-                            });
-
-                    if let Some(change) = change {
+                    if let Some(change) = self.determine_starvation(now, head, &snake_points) {
                         changes.push(change);
+                        continue;
+                    }
+
+                    match self.determine_immediate_decision(
+                        map,
+                        species,
+                        snake_points,
+                        uneaten_preys,
+                        max_size,
+                        all_segments,
+                        owning_head,
+                        all_snakes,
+                    ) {
+                        Some(SnakeDecision::Change(change)) => changes.push(change),
+                        Some(SnakeDecision::Pending(pending)) => movers.push(pending),
+                        None => {}
                     }
                 }
             }
@@ -201,29 +284,30 @@ impl SnakeSystem {
             changes.push(Change::Death(point));
         }
 
-        // Detect new snakes
+        // Detect new snakes, always starting from the lowest remaining point (rather than the
+        // `HashSet`'s randomized iteration order) so RNG draws stay reproducible under a fixed
+        // `Config::seed`
         let spare_parts = &mut segment_set.spare_parts;
-        while let Some(&point) = spare_parts.iter().next() {
+        while let Some(&point) = spare_parts.iter().min() {
             spare_parts.remove(&point);
             if let Some(snake_points) = self.determine_new_snake(point, spare_parts) {
                 changes.push(Change::NewSnake(snake_points));
             }
         }
 
-        changes
+        (changes, movers)
     }
 
-    fn apply_changes(&self, changes: Vec<Change>) {
-        let mut map = self.map.write(module_path!());
+    fn apply_changes(&self, map: &mut Map, changes: Vec<Change>) -> bool {
         let mut changed_map = false;
 
         for change in changes {
             match change {
                 Change::NewSnake(points) => {
-                    self.apply_new_snake(&mut map, &points);
+                    self.apply_new_snake(map, &points);
                 }
                 Change::Move { snake, target } => {
-                    self.apply_move(&mut map, &snake, target);
+                    self.apply_move(map, &snake, target);
                     changed_map = true;
                 }
                 Change::Eat {
@@ -231,25 +315,43 @@ impl SnakeSystem {
                     new_head,
                     food,
                 } => {
-                    self.apply_eat(&mut map, head, new_head, food);
+                    self.apply_eat(map, head, new_head, food);
                     changed_map = true;
                 }
                 Change::Death(point) => {
-                    self.apply_death(&mut map, point);
+                    self.apply_death(map, point);
                     changed_map = true;
                 }
                 Change::Starve(points) => {
-                    self.apply_starvation(&mut map, points);
+                    self.apply_starvation(map, points);
+                    changed_map = true;
+                }
+                Change::Combat {
+                    winner_head,
+                    loser_points,
+                } => {
+                    self.apply_combat(map, winner_head, loser_points);
                     changed_map = true;
                 }
             }
         }
 
-        if changed_map {
-            map.notify_update();
-        }
+        changed_map
+    }
+}
+
+impl EcosystemSystem for SnakeSystem {
+    fn interval_ticks(&self) -> usize {
+        self.interval_ticks
     }
 
+    fn step(&mut self, map: &mut Map) -> bool {
+        let changes = self.determine_changes(map);
+        self.apply_changes(map, changes)
+    }
+}
+
+impl SnakeSystem {
     /// Find a new snake that contains the given `point`. The snake orientation will be randomly
     /// chosen. Also, if there are multiple ambiguous snake formations, the result will be randomly
     /// determined.
@@ -328,6 +430,94 @@ impl SnakeSystem {
         (points.len() >= self.min_size).then_some(points)
     }
 
+    /// Like [`Self::extract_snake`], but reads the body segment set instead of draining it and
+    /// has no minimum-size requirement, since it is used to inspect a *different* species' snake
+    /// that this snake's own processing must not mutate.
+    fn extract_snake_chain(
+        max_size: usize,
+        head: Point,
+        head_next_segment: Option<Point>,
+        body_segment_set: &HashMap<Point, SnakeSegment>,
+    ) -> Vec<Point> {
+        let mut points = Vec::with_capacity(max_size);
+        points.push(head);
+        let mut next_segment = head_next_segment;
+
+        while let (Some(target), true) = (next_segment, points.len() < max_size) {
+            match body_segment_set.get(&target) {
+                None => break,
+                Some(snake_segment) => {
+                    points.push(target);
+                    next_segment = snake_segment.next_segment;
+                }
+            }
+        }
+
+        points
+    }
+
+    /// Check if a different-species snake is within striking distance: a body segment next to
+    /// `head` is an easy kill of the whole rival snake, while a head next to `head` is a duel
+    /// resolved by length, with equal lengths killing both. Ties among several adjacent rivals are
+    /// broken randomly.
+    fn determine_combat(
+        &mut self,
+        species: SnakeSpecies,
+        snake_points: &[Point],
+        all_segments: &HashMap<Point, SnakeSpecies>,
+        owning_head: &HashMap<Point, Point>,
+        all_snakes: &HashMap<SnakeSpecies, SnakeSegmentSet>,
+    ) -> Option<Change> {
+        let head = snake_points[0];
+
+        let rivals = Point::DIRECTIONS
+            .into_iter()
+            .map(|direction| head + direction)
+            .filter(|target| {
+                all_segments
+                    .get(target)
+                    .map(|&rival_species| rival_species != species)
+                    .unwrap_or(false)
+            })
+            .collect_vec();
+
+        let target = *rivals.choose(&mut self.rng)?;
+        let rival_species = all_segments[&target];
+        let rival_set = all_snakes.get(&rival_species)?;
+        let rival_head = *owning_head.get(&target)?;
+        let rival_head_segment = rival_set.heads.get(&rival_head)?;
+        let rival_points = Self::extract_snake_chain(
+            self.max_size(rival_species),
+            rival_head,
+            rival_head_segment.next_segment,
+            &rival_set.bodies,
+        );
+
+        if rival_head == target {
+            // Head-to-head: the longer snake survives, equal lengths kill both
+            Some(match snake_points.len().cmp(&rival_points.len()) {
+                Ordering::Greater => Change::Combat {
+                    winner_head: Some(head),
+                    loser_points: rival_points,
+                },
+                Ordering::Less => Change::Combat {
+                    winner_head: Some(rival_head),
+                    loser_points: snake_points.to_vec(),
+                },
+                Ordering::Equal => Change::Combat {
+                    winner_head: None,
+                    loser_points: [snake_points.to_vec(), rival_points].concat(),
+                },
+            })
+        } else {
+            // A body strike kills the whole rival snake and lets the attacker grow into its place
+            Some(Change::Combat {
+                winner_head: Some(head),
+                loser_points: rival_points,
+            })
+        }
+    }
+
     /// Check if the snake is starving
     fn determine_starvation(
         &self,
@@ -344,42 +534,50 @@ impl SnakeSystem {
         None
     }
 
-    /// Determine where the snake should next move to
-    fn determine_next_movement(
+    /// Decide what an individual snake does this tick, once starvation has already been ruled
+    /// out: fight a rival within striking distance regardless of the move roll, otherwise roll
+    /// the species' move ratio and either eat an adjacent prey or hand the snake off as a
+    /// [`PendingMove`] for the joint movement-scoring pass in [`Self::determine_movements`].
+    #[allow(clippy::too_many_arguments)]
+    fn determine_immediate_decision(
         &mut self,
         map: &Map,
         species: SnakeSpecies,
         snake_points: Vec<Point>,
         uneaten_preys: &mut HashSet<Point>,
         max_size: usize,
-    ) -> Option<Change> {
+        all_segments: &HashMap<Point, SnakeSpecies>,
+        owning_head: &HashMap<Point, Point>,
+        all_snakes: &HashMap<SnakeSpecies, SnakeSegmentSet>,
+    ) -> Option<SnakeDecision> {
+        // A rival snake within striking distance is dealt with regardless of the move roll below:
+        // staying still doesn't protect a snake from a neighbor that decides to bite
+        if let Some(change) = self.determine_combat(
+            species,
+            &snake_points,
+            all_segments,
+            owning_head,
+            all_snakes,
+        ) {
+            return Some(SnakeDecision::Change(change));
+        }
+
         if !self.rng.gen_bool(self.move_ratio(species)) {
             return None;
         }
 
         if snake_points.len() < max_size {
-            // Find a prey to eat
             let head = snake_points[0];
-            if let Some(change) = self.determine_eat_nearby_prey(map, uneaten_preys, head) {
-                return Some(change);
-            }
-
-            // Find the closest prey
-            let closest_preys = uneaten_preys
-                .iter()
-                .copied()
-                .min_set_by_key(|prey| prey.distance(head));
-            if let Some(prey) = closest_preys.choose(&mut self.rng).copied() {
-                let target = self.find_movement_target(map, head, prey)?;
-
-                return Some(Change::Move {
-                    snake: snake_points,
-                    target,
-                });
+            if let Some(change) =
+                self.determine_eat_nearby_prey(map, uneaten_preys, head, snake_points.len())
+            {
+                return Some(SnakeDecision::Change(change));
             }
         }
 
-        self.determine_random_walk(map, snake_points)
+        Some(SnakeDecision::Pending(PendingMove {
+            points: snake_points,
+        }))
     }
 
     /// Determine if can eat a nearby prey
@@ -388,13 +586,14 @@ impl SnakeSystem {
         map: &Map,
         uneaten_preys: &mut HashSet<Point>,
         head: Point,
+        snake_len: usize,
     ) -> Option<Change> {
         let food = head
-            .circle(self.eating_radius, map.size())
+            .euclidean_circle(self.eating_radius, map.size())
             .filter(|target| uneaten_preys.contains(target))
             .choose(&mut self.rng)?;
 
-        let new_head = self.find_movement_target(map, head, food)?;
+        let new_head = self.find_movement_target(map, head, food, snake_len)?;
         uneaten_preys.remove(&food);
         Some(Change::Eat {
             head,
@@ -403,45 +602,120 @@ impl SnakeSystem {
         })
     }
 
-    fn determine_random_walk(&mut self, map: &Map, snake_points: Vec<Point>) -> Option<Change> {
-        let head = snake_points[0];
-        let directions = if snake_points.len() == 1 {
-            vec![
-                (head + Point::X, 1.0),
-                (head + Point::Y, 1.0),
-                (head - Point::X, 1.0),
-                (head - Point::Y, 1.0),
-            ]
-        } else {
-            let forward = snake_points[0] - snake_points[1];
-            vec![
-                (head + forward, 4.0),
-                (head + forward.turn_right(), 1.0),
-                (head + forward.turn_left(), 1.0),
-            ]
-        };
-        let valid_targets = directions
+    /// Score and greedily assign ordinary moves across every species at once, instead of each
+    /// snake picking blind: the previous per-snake choice meant two heads could target the same
+    /// empty cell, and one of those `Move`s would silently no-op once [`Self::apply_move`]
+    /// re-checked occupancy at apply time.
+    ///
+    /// Each of a mover's legal targets is scored as a weighted sum of the scent-field gradient
+    /// (standing in for prey proximity, to avoid re-scanning every uneaten prey per candidate like
+    /// [`Self::determine_eat_nearby_prey`]'s radius search already does for adjacent food) and the
+    /// flood-filled free space reachable after the move. Movers are then resolved in descending
+    /// order of their own best score, each paying a collision penalty on cells an earlier, higher-
+    /// scoring mover already claimed, so a weaker mover routes around an already-reserved target
+    /// instead of blindly colliding with it.
+    fn determine_movements(&mut self, map: &Map, movers: Vec<PendingMove>) -> Vec<Change> {
+        let scored_targets = movers
+            .iter()
+            .map(|mover| self.score_candidate_targets(map, mover))
+            .collect_vec();
+
+        let best_scores = scored_targets
+            .iter()
+            .map(|targets| {
+                targets
+                    .iter()
+                    .map(|&(_, score)| score)
+                    .fold(f64::NEG_INFINITY, f64::max)
+            })
+            .collect_vec();
+
+        let mut order = (0..movers.len()).collect_vec();
+        order.sort_by(|&a, &b| best_scores[b].total_cmp(&best_scores[a]));
+
+        let mut claimed = HashSet::new();
+        let mut chosen_targets = vec![None; movers.len()];
+        for index in order {
+            let target = scored_targets[index]
+                .iter()
+                .map(|&(target, score)| {
+                    let penalty = if claimed.contains(&target) {
+                        self.move_collision_weight
+                    } else {
+                        0.0
+                    };
+                    (target, score - penalty)
+                })
+                .max_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(target, _)| target);
+
+            if let Some(target) = target {
+                claimed.insert(target);
+            }
+            chosen_targets[index] = target;
+        }
+
+        movers
+            .into_iter()
+            .zip(chosen_targets)
+            .filter_map(|(mover, target)| {
+                target.map(|target| Change::Move {
+                    snake: mover.points,
+                    target,
+                })
+            })
+            .collect()
+    }
+
+    /// Score every legal (empty, in-bounds) head move of `mover`; see
+    /// [`Self::determine_movements`] for how the weights are combined and collisions are resolved.
+    fn score_candidate_targets(&self, map: &Map, mover: &PendingMove) -> Vec<(Point, f64)> {
+        let head = mover.points[0];
+        let snake_len = mover.points.len();
+        let here_scent = map.scent().get(head).copied().unwrap_or(0.0);
+
+        Point::DIRECTIONS
             .into_iter()
-            .filter(|&(target, _)| {
+            .map(|direction| head + direction)
+            .filter(|&target| {
                 map.cells()
                     .get(target)
                     .map(|cell| cell.animal().is_empty())
                     .unwrap_or(false)
             })
-            .collect_vec();
+            .map(|target| {
+                let target_scent = map.scent().get(target).copied().unwrap_or(0.0);
+                let scent_score = (target_scent - here_scent) * self.move_scent_weight;
 
-        valid_targets
-            .choose_weighted(&mut self.rng, |&(_, weight)| weight)
-            .ok()
-            .map(|&(target, _)| Change::Move {
-                snake: snake_points,
-                target,
+                let area = Self::flood_fill_area(map, target, MAX_FLOOD_FILL_CELLS);
+                let space_score = Self::area_weight(area, snake_len) * self.move_space_weight;
+
+                (target, scent_score + space_score)
             })
+            .collect_vec()
     }
 
-    /// Find a valid movement that gets the snake closer to the given goal
-    fn find_movement_target(&mut self, map: &Map, head: Point, goal: Point) -> Option<Point> {
-        let best_moves = Point::DIRECTIONS
+    /// Find a valid movement that gets the snake closer to the given goal, routing around
+    /// obstacles via A* when possible and falling back to greedy single-step steering when no
+    /// path is found (or the search budget runs out)
+    fn find_movement_target(
+        &mut self,
+        map: &Map,
+        head: Point,
+        goal: Point,
+        snake_len: usize,
+    ) -> Option<Point> {
+        if let Some(target) = Self::find_path(map, head, goal) {
+            tracing::debug!(
+                "find_movement_target for {:?} towards {:?} = {:?} (A*)",
+                head,
+                goal,
+                target
+            );
+            return Some(target);
+        }
+
+        let candidates = Point::DIRECTIONS
             .into_iter()
             .map(|direction| head + direction)
             .filter(|&target| {
@@ -450,11 +724,24 @@ impl SnakeSystem {
                     .map(|cell| cell.animal().is_empty())
                     .unwrap_or(false)
             })
-            .min_set_by_key(|target| target.distance(goal));
+            .collect_vec();
+        let closest_distance = candidates.iter().map(|&target| target.distance(goal)).min();
+
+        let weighted_moves = candidates
+            .into_iter()
+            .filter(|&target| Some(target.distance(goal)) == closest_distance)
+            .map(|target| {
+                let area = Self::flood_fill_area(map, target, MAX_FLOOD_FILL_CELLS);
+                (target, Self::area_weight(area, snake_len))
+            })
+            .collect_vec();
 
-        let target = best_moves.choose(&mut self.rng).copied();
+        let target = weighted_moves
+            .choose_weighted(&mut self.rng, |&(_, weight)| weight)
+            .ok()
+            .map(|&(target, _)| target);
         tracing::debug!(
-            "find_movement_target for {:?} towards {:?} = {:?}",
+            "find_movement_target for {:?} towards {:?} = {:?} (greedy)",
             head,
             goal,
             target
@@ -462,6 +749,115 @@ impl SnakeSystem {
         target
     }
 
+    /// Search a grid path from `start` to `goal` with A*, returning only the first step off
+    /// `start`. Neighbors are the four [`Point::DIRECTIONS`] offsets that are in bounds and whose
+    /// cell is empty, except `goal` itself which is always considered passable (it holds the
+    /// prey). Cost per step is uniform (1) and the heuristic is [`Point::distance`], which is
+    /// admissible for this grid.
+    fn find_path(map: &Map, start: Point, goal: Point) -> Option<Point> {
+        let mut open = BinaryHeap::new();
+        let mut best_cost = HashMap::new();
+        let mut came_from: HashMap<Point, Point> = HashMap::new();
+        let mut expansions = 0;
+
+        best_cost.insert(start, 0usize);
+        open.push(Reverse((start.distance(goal), 0usize, start)));
+
+        while let Some(Reverse((_, cost, current))) = open.pop() {
+            if current == goal {
+                let mut step = current;
+                while let Some(&prev) = came_from.get(&step) {
+                    if prev == start {
+                        return Some(step);
+                    }
+                    step = prev;
+                }
+                return None;
+            }
+
+            if cost > *best_cost.get(&current).unwrap_or(&usize::MAX) {
+                // A cheaper path to `current` was already expanded
+                continue;
+            }
+
+            expansions += 1;
+            if expansions > MAX_PATHFINDING_EXPANSIONS {
+                return None;
+            }
+
+            for direction in Point::DIRECTIONS {
+                let neighbor = current + direction;
+                if neighbor != goal {
+                    let passable = map
+                        .cells()
+                        .get(neighbor)
+                        .map(|cell| cell.animal().is_empty())
+                        .unwrap_or(false);
+                    if !passable {
+                        continue;
+                    }
+                } else if map.cells().get(neighbor).is_none() {
+                    continue;
+                }
+
+                let tentative_cost = cost + 1;
+                if tentative_cost < *best_cost.get(&neighbor).unwrap_or(&usize::MAX) {
+                    best_cost.insert(neighbor, tentative_cost);
+                    came_from.insert(neighbor, current);
+                    let f = tentative_cost + neighbor.distance(goal);
+                    open.push(Reverse((f, tentative_cost, neighbor)));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Count the cells reachable from `start` through empty cells via breadth-first flood fill,
+    /// stopping early once `cap` cells have been counted. Used to avoid steering a snake into a
+    /// pocket too small for its own body.
+    fn flood_fill_area(map: &Map, start: Point, cap: usize) -> usize {
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::new();
+        visited.insert(start);
+        queue.push_back(start);
+
+        while visited.len() < cap {
+            let Some(point) = queue.pop_front() else {
+                break;
+            };
+
+            for direction in Point::DIRECTIONS {
+                let neighbor = point + direction;
+                if visited.contains(&neighbor) {
+                    continue;
+                }
+
+                let empty = map
+                    .cells()
+                    .get(neighbor)
+                    .map(|cell| cell.animal().is_empty())
+                    .unwrap_or(false);
+                if empty {
+                    visited.insert(neighbor);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        visited.len().min(cap)
+    }
+
+    /// Turn a flood-filled reachable area into a `choose_weighted` weight: pockets smaller than
+    /// the snake itself are heavily down-weighted, since entering one means sealing itself in
+    fn area_weight(area: usize, snake_len: usize) -> f64 {
+        if area < snake_len {
+            0.01
+        } else {
+            area as f64
+        }
+    }
+
     fn max_size(&self, species: SnakeSpecies) -> usize {
         match species {
             SnakeSpecies::A => self.a_max_size,
@@ -617,6 +1013,78 @@ impl SnakeSystem {
         *food = CellAnimal::Empty;
     }
 
+    /// Kill every point in `loser_points`. If `winner_head` is `Some`, right before mutating this
+    /// re-checks that the winner is still a head, that a loser point is still adjacent to it and
+    /// that every loser point still holds a snake of one species other than the winner's, since
+    /// the changes were computed under a read lock and may no longer match the map by the time the
+    /// write lock is held; the loser points' segment linkage is not re-checked. The winner then
+    /// grows into that adjacent loser point, exactly like [`Self::apply_eat`] grows a snake onto a
+    /// food cell. If `winner_head` is `None` (a draw), every loser point that still holds a snake
+    /// dies, with no further checks.
+    fn apply_combat(&self, map: &mut Map, winner_head: Option<Point>, loser_points: Vec<Point>) {
+        let Some(winner_head) = winner_head else {
+            // A head-to-head draw: both snakes die, nobody grows
+            for point in loser_points {
+                self.apply_death(map, point);
+            }
+            return;
+        };
+
+        let Some(winner) = map.cells()[winner_head].animal().snake() else {
+            return;
+        };
+        let winner_species = winner.species;
+        let Some(winner_segment) = winner.segment else {
+            return;
+        };
+        let last_feeding = match winner_segment.kind {
+            SnakeSegmentKind::Body => return,
+            SnakeSegmentKind::Head { last_feeding } => last_feeding,
+        };
+
+        // The winner grows into whichever loser point is still adjacent to it
+        let Some(&entry_point) = loser_points
+            .iter()
+            .find(|&&point| Point::DIRECTIONS.iter().any(|&d| winner_head + d == point))
+        else {
+            return;
+        };
+
+        let Some(loser_species) = map.cells()[entry_point].animal().snake().map(|s| s.species)
+        else {
+            return;
+        };
+        if loser_species == winner_species {
+            return;
+        }
+        let is_valid_loser = loser_points.iter().all(|&point| {
+            map.cells()[point].animal().snake().map(|s| s.species) == Some(loser_species)
+        });
+        if !is_valid_loser {
+            return;
+        }
+
+        for &point in &loser_points {
+            if point != entry_point {
+                self.apply_death(map, point);
+            }
+        }
+
+        let (old_head, new_head) = map.two_cells_mut(winner_head, entry_point);
+        *new_head.animal_mut() = CellAnimal::Snake(Box::new(Snake {
+            species: winner_species,
+            segment: Some(SnakeSegment {
+                kind: SnakeSegmentKind::Head { last_feeding },
+                next_segment: Some(winner_head),
+            }),
+        }));
+        if let Some(old_head) = old_head.animal_mut().snake_mut() {
+            if let Some(old_head) = &mut old_head.segment {
+                old_head.kind = SnakeSegmentKind::Body;
+            }
+        }
+    }
+
     fn apply_death(&self, map: &mut Map, point: Point) {
         let cell = map.cells_mut()[point].animal_mut();
 
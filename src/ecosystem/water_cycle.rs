@@ -1,120 +1,157 @@
 use crate::config::Config;
+use crate::ecosystem::scheduler::EcosystemSystem;
 use crate::map::Map;
 use crate::point::Point;
 use itertools::Itertools;
 use rand::distributions::Bernoulli;
 use rand::prelude::{Distribution, SliceRandom, SmallRng};
-use rand::{Rng, SeedableRng};
-use std::sync::{Arc, RwLock};
-use std::time::Duration;
-use tokio::time;
+use rand::Rng;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::Arc;
+
+/// Arbitrary, only needs to differ from other systems' salts so their derived RNG streams
+/// diverge even when [`Config::seed`] is shared
+const RNG_SALT: u64 = 1;
+
+/// Tracks one of [`WaterCycleSystem`]'s two periodic actions (raining or evaporating), each of
+/// which used to run in an alternating, self-contained burst of ticks before the unified
+/// scheduler existed. Since a single [`EcosystemSystem::step`] call can't block across several
+/// scheduler ticks, rain and evaporation now each advance on their own cadence independently
+/// instead of taking turns, but still re-roll a randomized cycle length the same way the old
+/// bursts did, so the ratio applied on every tick is still derived from "how much should happen
+/// over this cycle, spread evenly".
+#[derive(Debug)]
+struct Cycle {
+    /// Scheduler ticks between two consecutive actions
+    tick_interval: usize,
+    /// Scheduler ticks left before the next action
+    ticks_until_next: usize,
+    /// Actions left in the current randomized cycle before [`Self::ratio_per_tick`] is re-rolled
+    remaining_ticks: u32,
+    ratio_per_tick: f64,
+}
+
+impl Cycle {
+    fn new(tick_interval: usize) -> Self {
+        Self {
+            tick_interval,
+            ticks_until_next: 0,
+            remaining_ticks: 0,
+            ratio_per_tick: 0.0,
+        }
+    }
+
+    /// Advance by one scheduler tick, returning the ratio to apply this tick once it's actually
+    /// due, or `None` if this system's interval hasn't elapsed yet
+    fn advance(
+        &mut self,
+        rng: &mut SmallRng,
+        min_cycle_ticks: usize,
+        max_cycle_ticks: usize,
+        overall_ratio: f64,
+        label: &str,
+    ) -> Option<f64> {
+        if self.ticks_until_next > 0 {
+            self.ticks_until_next -= 1;
+            return None;
+        }
+
+        if self.remaining_ticks == 0 {
+            let cycle_ticks = rng
+                .gen_range(min_cycle_ticks..=max_cycle_ticks)
+                .max(self.tick_interval);
+            self.remaining_ticks = (cycle_ticks / self.tick_interval).max(1) as u32;
+            self.ratio_per_tick =
+                1.0 - (1.0 - overall_ratio).powf(1.0 / self.remaining_ticks as f64);
+            tracing::info!(
+                "Will {} {}% of the water each tick",
+                label,
+                100.0 * self.ratio_per_tick
+            );
+        }
+
+        self.remaining_ticks -= 1;
+        self.ticks_until_next = self.tick_interval - 1;
+        Some(self.ratio_per_tick)
+    }
+}
 
 #[derive(Debug)]
 pub struct WaterCycleSystem {
-    min_cycle: Duration,
-    max_cycle: Duration,
+    min_cycle_ticks: usize,
+    max_cycle_ticks: usize,
     evaporation_ratio: f64,
-    evaporation_tick: Duration,
     rain_ratio: f64,
-    rain_tick: Duration,
     max_rain_radius: usize,
-    map: Arc<RwLock<Map>>,
     rng: SmallRng,
-    atmosphere_water: i32,
+    rain: Cycle,
+    evaporation: Cycle,
+    /// Shared with callers outside this system (e.g. a snapshot writer) via
+    /// [`Self::atmosphere_water_handle`], so both can observe and, before the system starts
+    /// running, override the budget without needing a lock
+    atmosphere_water: Arc<AtomicI32>,
 }
 
 impl WaterCycleSystem {
-    pub fn new(config: &Config, map: Arc<RwLock<Map>>) -> Self {
-        let size = map.read().unwrap().size() as f64;
+    pub fn new(config: &Config) -> Self {
+        let size = config.map_size as f64;
         let atmosphere_water = config.water_in_atmosphere_ratio * size * size;
+
         Self {
-            min_cycle: Duration::from_secs(config.water_min_cycle_seconds),
-            max_cycle: Duration::from_secs(config.water_max_cycle_seconds),
+            min_cycle_ticks: config.ticks(config.water_min_cycle_seconds),
+            max_cycle_ticks: config.ticks(config.water_max_cycle_seconds),
             evaporation_ratio: config.water_evaporation_ratio,
-            evaporation_tick: Duration::from_secs(config.water_evaporation_tick_seconds),
             rain_ratio: config.water_rain_ratio,
-            rain_tick: Duration::from_secs(config.water_rain_tick_seconds),
             max_rain_radius: config.water_max_rain_radius,
-            map,
-            rng: SmallRng::from_entropy(),
-            atmosphere_water: atmosphere_water.round() as i32,
+            rng: config.system_rng(RNG_SALT),
+            rain: Cycle::new(config.ticks(config.water_rain_tick_seconds)),
+            evaporation: Cycle::new(config.ticks(config.water_evaporation_tick_seconds)),
+            atmosphere_water: Arc::new(AtomicI32::new(atmosphere_water.round() as i32)),
         }
     }
 
-    pub async fn run(mut self) {
-        loop {
-            self.rain().await;
-            self.evaporate().await;
-        }
+    /// A handle to the live atmosphere water budget, shared with this system rather than copied:
+    /// reading it reflects whatever the system has evaporated or rained out so far, and writing
+    /// it (only safe before the scheduler starts ticking) restores a budget saved in a snapshot
+    pub fn atmosphere_water_handle(&self) -> Arc<AtomicI32> {
+        self.atmosphere_water.clone()
     }
 
-    async fn evaporate(&mut self) {
-        let cycle_duration = self.rng.gen_range(self.min_cycle..=self.max_cycle);
-        let num_ticks =
-            (cycle_duration.as_secs_f64() / self.evaporation_tick.as_secs_f64()).ceil() as i32;
-        let ratio_per_tick = 1.0 - (1.0 - self.evaporation_ratio).powf(1.0 / num_ticks as f64);
-        tracing::info!(
-            "Will evaporate {}% of the water each tick",
-            100.0 * ratio_per_tick
-        );
-        let Ok(random) = Bernoulli::new(ratio_per_tick) else { return };
-
-        for _ in 0..num_ticks {
-            {
-                let mut map = self.map.write().unwrap();
-
-                for cell in map.cells_mut() {
-                    if let Some(drier) = cell.water().drier() {
-                        if random.sample(&mut self.rng) {
-                            cell.set_water(drier);
-                            self.atmosphere_water += 1;
-                        }
-                    }
-                }
+    fn evaporate_tick(&mut self, map: &mut Map, ratio_per_tick: f64) {
+        let Ok(random) = Bernoulli::new(ratio_per_tick) else {
+            return;
+        };
 
-                map.notify_update();
+        for cell in map.cells_mut() {
+            if let Some(drier) = cell.water().drier() {
+                if random.sample(&mut self.rng) {
+                    cell.set_water(drier);
+                    self.atmosphere_water.fetch_add(1, Ordering::Relaxed);
+                }
             }
-
-            time::sleep(self.evaporation_tick).await;
         }
     }
 
-    async fn rain(&mut self) {
-        let cycle_duration = self.rng.gen_range(self.min_cycle..=self.max_cycle);
-        let num_ticks = (cycle_duration.as_secs_f64() / self.rain_tick.as_secs_f64()).ceil() as i32;
-        let ratio_per_tick = 1.0 - (1.0 - self.rain_ratio).powf(1.0 / num_ticks as f64);
-        tracing::info!(
-            "Will rain {}% of the water each tick",
-            100.0 * ratio_per_tick
-        );
-
-        for _ in 0..num_ticks {
-            {
-                let mut map = self.map.write().unwrap();
-
-                let mut remaining_rain =
-                    (self.atmosphere_water as f64 * ratio_per_tick).ceil() as i32;
-                let mut radius = 0;
-                let center_x = self.rng.gen_range(0..map.size());
-                let center_y = self.rng.gen_range(0..map.size());
-                let center = Point::new(center_x, center_y);
-                while remaining_rain > 0 && radius <= self.max_rain_radius {
-                    let mut candidates = center.circumference(radius, map.size()).collect_vec();
-                    candidates.shuffle(&mut self.rng);
-                    Self::add_rain(
-                        &mut map,
-                        &candidates,
-                        &mut remaining_rain,
-                        &mut self.atmosphere_water,
-                    );
-
-                    radius += 1;
-                }
-
-                map.notify_update();
-            }
-
-            time::sleep(self.rain_tick).await;
+    fn rain_tick(&mut self, map: &mut Map, ratio_per_tick: f64) {
+        let atmosphere_water = self.atmosphere_water.load(Ordering::Relaxed);
+        let mut remaining_rain = (atmosphere_water as f64 * ratio_per_tick).ceil() as i32;
+        let mut radius = 0;
+        let center_x = self.rng.gen_range(0..map.size());
+        let center_y = self.rng.gen_range(0..map.size());
+        let center = Point::new(center_x, center_y);
+        while remaining_rain > 0 && radius <= self.max_rain_radius {
+            let mut candidates = center
+                .euclidean_circumference(radius, map.size())
+                .collect_vec();
+            candidates.shuffle(&mut self.rng);
+            Self::add_rain(
+                map,
+                &candidates,
+                &mut remaining_rain,
+                &self.atmosphere_water,
+            );
+
+            radius += 1;
         }
     }
 
@@ -122,7 +159,7 @@ impl WaterCycleSystem {
         map: &mut Map,
         candidates: &[Point],
         remaining_rain: &mut i32,
-        atmosphere_water: &mut i32,
+        atmosphere_water: &AtomicI32,
     ) {
         for &candidate in candidates {
             if *remaining_rain <= 0 {
@@ -133,8 +170,38 @@ impl WaterCycleSystem {
             if let Some(wetter) = cell.water().wetter() {
                 cell.set_water(wetter);
                 *remaining_rain -= 1;
-                *atmosphere_water -= 1;
+                atmosphere_water.fetch_sub(1, Ordering::Relaxed);
             }
         }
     }
 }
+
+impl EcosystemSystem for WaterCycleSystem {
+    fn step(&mut self, map: &mut Map) -> bool {
+        let mut changed = false;
+
+        if let Some(ratio) = self.rain.advance(
+            &mut self.rng,
+            self.min_cycle_ticks,
+            self.max_cycle_ticks,
+            self.rain_ratio,
+            "rain",
+        ) {
+            self.rain_tick(map, ratio);
+            changed = true;
+        }
+
+        if let Some(ratio) = self.evaporation.advance(
+            &mut self.rng,
+            self.min_cycle_ticks,
+            self.max_cycle_ticks,
+            self.evaporation_ratio,
+            "evaporate",
+        ) {
+            self.evaporate_tick(map, ratio);
+            changed = true;
+        }
+
+        changed
+    }
+}
@@ -1,18 +1,53 @@
 use crate::cell::CellWater;
+use crate::cell::WaterFlowState;
 use crate::config::Config;
+use crate::ecosystem::scheduler::EcosystemSystem;
 use crate::map::Map;
+use crate::point::Point;
 use ndarray::Array2;
-use std::sync::{Arc, RwLock};
-use std::time::Duration;
-use tokio::time;
+use serde::Deserialize;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::Arc;
+
+/// How [`WaterFlowSystem`] moves water across the map each tick.
+#[derive(Debug, Clone, Copy, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum WaterFlowMode {
+    /// Move at most one unit of water per cell per tick towards a lower neighbor, see
+    /// [`WaterFlowSystem::flow_incrementally`]. Never quite settles, leaving puddles on slopes.
+    Incremental,
+    /// Recompute, every tick, the elevation every basin would fill up to if it were a real lake,
+    /// see [`WaterFlowSystem::settle_to_equilibrium`].
+    Equilibrium,
+    /// Treat each cell's height plus however much water it holds as a continuous "surface
+    /// level" and move a fraction of the difference to the lowest neighbor(s) every tick, see
+    /// [`WaterFlowSystem::flow_proportionally`]. Drains steep slopes faster than gentle ones,
+    /// unlike [`Self::Incremental`]'s fixed one-unit-per-tick steps.
+    Proportional,
+}
 
 #[derive(Debug)]
 pub struct WaterFlowSystem {
-    map: Arc<RwLock<Map>>,
     water_flows: Array2<WaterFlow>,
-    tick_sleep: Duration,
+    interval_ticks: usize,
     tick: usize,
     water_thickness: i16,
+    transfer_fraction: f64,
+    mode: WaterFlowMode,
+    /// Shared with [`crate::ecosystem::water_cycle::WaterCycleSystem`] via
+    /// [`crate::ecosystem::water_cycle::WaterCycleSystem::atmosphere_water_handle`], so
+    /// [`Self::settle_to_equilibrium`] can draw on and credit back to the same budget
+    /// `WaterCycleSystem` rains from, instead of treating on-map water as a separate ledger
+    atmosphere_water: Arc<AtomicI32>,
+    /// The sub-unit remainder [`Self::flow_proportionally`] couldn't represent in `CellWater`'s
+    /// integer levels this tick, carried over so the next tick's transfer starts from the true
+    /// continuous level instead of the rounded-off one. Without this, re-quantizing every
+    /// positive fraction up to `Shallow` would manufacture a whole unit of water out of any
+    /// transfer, however small, and quantizing down would lose it instead; carrying the
+    /// remainder keeps the total conserved across ticks.
+    proportional_pending: Array2<f64>,
 }
 
 #[derive(Debug)]
@@ -32,34 +67,26 @@ struct WaterFlowTarget {
 }
 
 impl WaterFlowSystem {
-    pub fn new(config: &Config, map: Arc<RwLock<Map>>) -> Self {
-        let water_flows = Self::determine_water_flows(
-            config.water_flow_max_radius,
-            config.water_thickness,
-            &map.read().unwrap(),
-        );
+    pub fn new(config: &Config, map: &Map, atmosphere_water: Arc<AtomicI32>) -> Self {
+        let water_flows =
+            Self::determine_water_flows(config.water_flow_max_radius, config.water_thickness, map);
 
         Self {
-            map,
             water_flows,
-            tick_sleep: Duration::from_secs(config.water_flow_tick_seconds),
+            interval_ticks: config.ticks(config.water_flow_tick_seconds),
             tick: 0,
             water_thickness: config.water_thickness as i16,
+            transfer_fraction: config.water_flow_transfer_fraction,
+            mode: config.water_flow_mode,
+            atmosphere_water,
+            proportional_pending: Array2::from_elem(map.cells().dim(), 0.0),
         }
     }
 
-    pub async fn run(mut self) {
-        loop {
-            self.flow();
-            time::sleep(self.tick_sleep).await;
-        }
-    }
-
-    fn flow(&mut self) {
+    fn flow_incrementally(&mut self, map: &mut Map) -> bool {
         self.tick += 1;
 
         let this_tick = self.tick;
-        let mut map = self.map.write().unwrap();
         let cells = map.cells_mut();
         let mut flowed = 0;
 
@@ -69,6 +96,7 @@ impl WaterFlowSystem {
                 continue;
             }
 
+            let mut transferred = false;
             if let Some(drier) = cells[source].water().drier() {
                 for &target in flow.targets.iter() {
                     let target_cell = &mut cells[target.coordinates];
@@ -83,8 +111,11 @@ impl WaterFlowSystem {
 
                         if target.fall > min_fall {
                             target_cell.set_water(wetter);
+                            target_cell.set_water_flow(WaterFlowState::Running);
                             cells[source].set_water(drier);
+                            cells[source].set_water_flow(WaterFlowState::Running);
                             flowed += 1;
+                            transferred = true;
                             self.water_flows[target.coordinates]
                                 .last_received_tick
                                 .set(this_tick);
@@ -93,11 +124,244 @@ impl WaterFlowSystem {
                     }
                 }
             }
+
+            // No viable lower target this tick: the water sitting here, if any, is at rest
+            if !transferred {
+                cells[source].set_water_flow(WaterFlowState::Settled);
+            }
+        }
+
+        // A cell visited earlier in the loop above, before the source that flows into it, gets
+        // tagged `Settled` by its own no-downhill-target check and only afterwards receives
+        // water; re-assert `Running` for every receiver here instead of relying on that tag
+        // surviving whatever order the main pass happened to visit cells in
+        for (coordinates, flow) in self.water_flows.indexed_iter() {
+            if flow.last_received_tick.get() == this_tick {
+                cells[coordinates].set_water_flow(WaterFlowState::Running);
+            }
         }
 
         if flowed > 0 {
             tracing::info!("Flowed {} water", flowed);
-            map.notify_update();
+        }
+
+        flowed > 0
+    }
+
+    /// Recompute, for the whole map, the elevation water would settle to via the Priority-Flood
+    /// algorithm: every border cell is a spillway, so flooding inwards from the border tells us,
+    /// for each cell, the lowest elevation a connected body of water could reach there before
+    /// escaping off the edge of the map. Basins fill up flat to that elevation, the way a real
+    /// lake settles, instead of moving one unit of water per cell per tick like
+    /// [`Self::flow_incrementally`].
+    fn settle_to_equilibrium(&mut self, map: &mut Map) -> bool {
+        self.tick += 1;
+
+        let (rows, cols) = map.cells().dim();
+        let mut water_level = Array2::from_elem((rows, cols), i16::MAX);
+        let mut closed = Array2::from_elem((rows, cols), false);
+        let mut heap = BinaryHeap::new();
+
+        for i in 0..rows {
+            for j in 0..cols {
+                if i == 0 || j == 0 || i == rows - 1 || j == cols - 1 {
+                    let point = Point::new_ij((i, j));
+                    let height = map.cells()[point].height() as i16;
+                    water_level[point] = height;
+                    closed[point] = true;
+                    heap.push(Reverse((height, point.y, point.x)));
+                }
+            }
+        }
+
+        // Every cell is pushed onto the heap at most once, right when `closed` flips to `true`,
+        // so there is nothing left to re-pop and ties in elevation cannot cause a loop
+        while let Some(Reverse((level, y, x))) = heap.pop() {
+            let point = Point { x, y };
+            for direction in Point::DIRECTIONS {
+                let neighbor = point + direction;
+                match closed.get(neighbor) {
+                    Some(&false) => {}
+                    _ => continue,
+                }
+
+                let neighbor_level = (map.cells()[neighbor].height() as i16).max(level);
+                water_level[neighbor] = neighbor_level;
+                closed[neighbor] = true;
+                heap.push(Reverse((neighbor_level, neighbor.y, neighbor.x)));
+            }
+        }
+
+        // The water available to redistribute this tick is the whole system's water budget: what
+        // is already sitting on the map, plus whatever `WaterCycleSystem` is currently holding in
+        // its atmosphere reservoir. Settling to equilibrium only reshapes where that budget sits
+        // (on the map vs. in the atmosphere), it never creates or destroys any, so whatever isn't
+        // placed on the map by the end of this function is credited back to the atmosphere
+        // instead of being silently discarded.
+        let on_map_water: i64 = map
+            .cells()
+            .iter()
+            .map(|cell| Self::water_units(cell.water()))
+            .sum();
+        let total_budget = on_map_water + self.atmosphere_water.load(Ordering::Relaxed) as i64;
+        let mut available_units = total_budget;
+
+        // Fill the deepest basins first, so a limited budget settles into the lakes that would
+        // hold the most water rather than spreading thin puddles over every shallow depression
+        let mut order: Vec<(usize, usize)> = (0..rows)
+            .flat_map(|i| (0..cols).map(move |j| (i, j)))
+            .collect();
+        order.sort_by_key(|&coordinates| {
+            let excess = water_level[coordinates] - map.cells()[coordinates].height() as i16;
+            (Reverse(excess), coordinates)
+        });
+
+        let cells = map.cells_mut();
+        let mut changed = 0;
+        for coordinates in order {
+            let excess = water_level[coordinates] - cells[coordinates].height() as i16;
+            let desired = if excess >= self.water_thickness && available_units >= 2 {
+                CellWater::Deep
+            } else if excess > 0 && available_units >= 1 {
+                CellWater::Shallow
+            } else {
+                CellWater::Empty
+            };
+            available_units -= Self::water_units(desired);
+
+            if Self::water_units(cells[coordinates].water()) != Self::water_units(desired) {
+                changed += 1;
+            }
+            cells[coordinates].set_water(desired);
+            // Equilibrium mode jumps straight to a resting state, there is no in-between tick
+            // where water is actively moving
+            cells[coordinates].set_water_flow(WaterFlowState::Settled);
+        }
+
+        // Whatever of the budget wasn't placed on the map (because every basin is already full at
+        // its spill point) goes back into the atmosphere, so the combined map-plus-atmosphere
+        // total stays conserved instead of draining away tick after tick
+        self.atmosphere_water.store(
+            available_units.clamp(0, i32::MAX as i64) as i32,
+            Ordering::Relaxed,
+        );
+
+        if changed > 0 {
+            tracing::info!("Settled {} cells to equilibrium", changed);
+        }
+
+        changed > 0
+    }
+
+    /// Redistribute water as a continuous "surface level" (height plus however much water a cell
+    /// holds, in the same units as [`Self::water_thickness`]): every cell computes its own
+    /// surface level from a stable snapshot, then sends [`Self::transfer_fraction`] of half the
+    /// difference (capped at the water it holds) to whichever of its four [`Point::DIRECTIONS`]
+    /// neighbors has the lowest, strictly lower surface level, split evenly among neighbors tied
+    /// for lowest so symmetric terrain drains symmetrically. Lower neighbors that are not the
+    /// lowest receive nothing. Transfers are accumulated into a delta buffer and only applied in a
+    /// second pass, so every cell's send this tick is computed from the same starting snapshot. The
+    /// result is then re-quantized back into `Empty`/`Shallow`/`Deep`, exactly like
+    /// [`Self::settle_to_equilibrium`], except the sub-unit remainder that re-quantizing can't
+    /// represent is kept in [`Self::proportional_pending`] instead of being rounded away, so a
+    /// trickle of fractional transfers accumulates into a real unit over several ticks rather
+    /// than manufacturing (or losing) water every single tick.
+    fn flow_proportionally(&mut self, map: &mut Map) -> bool {
+        self.tick += 1;
+
+        let cells = map.cells();
+        let dim = cells.dim();
+        let previous_water = Array2::from_shape_fn(dim, |coordinates| {
+            Self::water_units(cells[coordinates].water()) as f64
+                + self.proportional_pending[coordinates]
+        });
+        let surface = Array2::from_shape_fn(dim, |coordinates| {
+            cells[coordinates].height() as f64 + previous_water[coordinates]
+        });
+        let mut delta = Array2::from_elem(dim, 0.0);
+
+        for ((i, j), &level) in previous_water.indexed_iter() {
+            if level <= 0.0 {
+                continue;
+            }
+
+            let point = Point::new_ij((i, j));
+            let here_surface = surface[(i, j)];
+            let lower_neighbors: Vec<Point> = Point::DIRECTIONS
+                .into_iter()
+                .map(|direction| point + direction)
+                .filter(|&neighbor| {
+                    surface
+                        .get(neighbor)
+                        .map(|&neighbor_surface| neighbor_surface < here_surface)
+                        .unwrap_or(false)
+                })
+                .collect();
+            if lower_neighbors.is_empty() {
+                continue;
+            }
+
+            let lowest_surface = lower_neighbors
+                .iter()
+                .fold(f64::INFINITY, |lowest, &neighbor| {
+                    lowest.min(surface[neighbor])
+                });
+            let tied_lowest: Vec<Point> = lower_neighbors
+                .into_iter()
+                .filter(|&neighbor| surface[neighbor] == lowest_surface)
+                .collect();
+
+            // Clamped so this cell never sends more than half of what separates it from the
+            // lowest tied neighbor (keeping the transfer symmetric instead of overshooting past
+            // equilibrium) and never more water than it actually holds
+            let total_transfer = (level.min((here_surface - lowest_surface) / 2.0)
+                * self.transfer_fraction)
+                .max(0.0);
+            let share = total_transfer / tied_lowest.len() as f64;
+
+            delta[(i, j)] -= total_transfer;
+            for neighbor in tied_lowest {
+                delta[neighbor] += share;
+            }
+        }
+
+        let cells = map.cells_mut();
+        let mut changed = 0;
+        for (coordinates, cell) in cells.indexed_iter_mut() {
+            let new_level = (previous_water[coordinates] + delta[coordinates]).max(0.0);
+            let desired = if new_level >= self.water_thickness as f64 {
+                CellWater::Deep
+            } else if new_level > 0.0 {
+                CellWater::Shallow
+            } else {
+                CellWater::Empty
+            };
+            self.proportional_pending[coordinates] = new_level - Self::water_units(desired) as f64;
+
+            let flowing = Self::water_units(cell.water()) != Self::water_units(desired);
+            if flowing {
+                changed += 1;
+            }
+            cell.set_water(desired);
+            cell.set_water_flow(if flowing {
+                WaterFlowState::Running
+            } else {
+                WaterFlowState::Settled
+            });
+        }
+
+        if changed > 0 {
+            tracing::info!("Flowed {} cells proportionally", changed);
+        }
+
+        changed > 0
+    }
+
+    fn water_units(water: CellWater) -> i64 {
+        match water {
+            CellWater::Empty => 0,
+            CellWater::Shallow => 1,
+            CellWater::Deep => 2,
         }
     }
 
@@ -154,3 +418,17 @@ impl WaterFlowSystem {
         })
     }
 }
+
+impl EcosystemSystem for WaterFlowSystem {
+    fn interval_ticks(&self) -> usize {
+        self.interval_ticks
+    }
+
+    fn step(&mut self, map: &mut Map) -> bool {
+        match self.mode {
+            WaterFlowMode::Incremental => self.flow_incrementally(map),
+            WaterFlowMode::Equilibrium => self.settle_to_equilibrium(map),
+            WaterFlowMode::Proportional => self.flow_proportionally(map),
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use crate::cell_color::CellColor;
+use crate::keyframes::KeyframeStore;
+use crate::map;
+use crate::monitored_rwlock::MonitoredRwLock;
+use crate::web_error::WebError;
+use anyhow::Context;
+use axum::extract::{Query, State};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Debug, Deserialize)]
+pub struct Request {
+    index: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Response {
+    timestamp: u64,
+    version_id: String,
+    size: usize,
+    colors: Vec<[u8; 3]>,
+    cell_color_indexes: Vec<usize>,
+}
+
+pub async fn get_keyframe(
+    Query(request): Query<Request>,
+    State(store): State<Arc<MonitoredRwLock<KeyframeStore>>>,
+) -> Result<Json<Response>, WebError> {
+    let store = store.read(module_path!());
+    let keyframe = store
+        .get(request.index)
+        .context("no keyframe at this index")
+        .map_err(WebError::bad_request)?;
+
+    let (version_id, size, cell_color_indexes) = map::decode_packed(&keyframe.packed)?;
+
+    Ok(Json(Response {
+        timestamp: keyframe.timestamp,
+        version_id,
+        size,
+        colors: CellColor::ALL_COLORS
+            .iter()
+            .map(|color| color.as_rgb())
+            .collect(),
+        cell_color_indexes,
+    }))
+}
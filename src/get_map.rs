@@ -3,6 +3,9 @@ use crate::config::Config;
 use crate::map::Map;
 use crate::monitored_rwlock::MonitoredRwLock;
 use axum::extract::{Query, State};
+use axum::http::header::{ACCEPT, CONTENT_TYPE};
+use axum::http::HeaderMap;
+use axum::response::{IntoResponse, Response as AxumResponse};
 use axum::Json;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -12,31 +15,76 @@ use tokio::time;
 #[derive(Debug, Deserialize)]
 pub struct Request {
     last_version_id: Option<String>,
+    format: Option<Format>,
+}
+
+/// How the map should be encoded on the wire, selected via the `?format=` query param or, failing
+/// that, the `Accept` header.
+#[derive(Debug, Clone, Copy, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum Format {
+    /// The default, human-readable encoding; one `usize` per cell.
+    Json,
+    /// 4 bits per cell, see [`Map::encode_packed`].
+    Binary,
+    /// Run-length-encoded, see [`Map::encode_rle`].
+    Rle,
+}
+
+impl Format {
+    fn from_accept_header(headers: &HeaderMap) -> Self {
+        let accept = headers
+            .get(ACCEPT)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+
+        if accept.contains("application/x-nature-pixel-rle") {
+            Format::Rle
+        } else if accept.contains("application/octet-stream") {
+            Format::Binary
+        } else {
+            Format::Json
+        }
+    }
 }
 
 #[derive(Debug, Serialize)]
-pub struct Response {
-    version_id: String,
-    size: usize,
-    colors: Vec<[u8; 3]>,
-    available_color_indexes: Vec<usize>,
-    cell_color_indexes: Vec<usize>,
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Response {
+    Full {
+        version_id: String,
+        size: usize,
+        colors: Vec<[u8; 3]>,
+        available_color_indexes: Vec<usize>,
+        cell_color_indexes: Vec<usize>,
+    },
+    Delta {
+        version_id: String,
+        changed_cells: Vec<(usize, usize)>,
+    },
 }
 
 pub async fn get_map(
     Query(request): Query<Request>,
+    headers: HeaderMap,
     State(map): State<Arc<MonitoredRwLock<Map>>>,
     State(config): State<Arc<Config>>,
-) -> Json<Response> {
+) -> AxumResponse {
+    let format = request
+        .format
+        .unwrap_or_else(|| Format::from_accept_header(&headers));
+
     let change_notifier;
     {
         let map_lock = map.read(module_path!());
 
-        match request.last_version_id {
+        match &request.last_version_id {
             Some(last_version_id) if last_version_id == map_lock.version_id() => {
                 // Long pooling: wait for change
             }
-            _ => return prepare_response(&map_lock),
+            _ => {
+                return prepare_response(&map_lock, request.last_version_id.as_deref(), format)
+            }
         }
 
         change_notifier = map_lock.change_notifier().clone();
@@ -48,11 +96,44 @@ pub async fn get_map(
     )
     .await;
 
-    prepare_response(&map.read(module_path!()))
+    prepare_response(
+        &map.read(module_path!()),
+        request.last_version_id.as_deref(),
+        format,
+    )
+}
+
+fn prepare_response(
+    map: &Map,
+    last_version_id: Option<&str>,
+    format: Format,
+) -> AxumResponse {
+    match format {
+        Format::Binary => (
+            [(CONTENT_TYPE, "application/octet-stream")],
+            map.encode_packed(),
+        )
+            .into_response(),
+        Format::Rle => (
+            [(CONTENT_TYPE, "application/octet-stream")],
+            map.encode_rle(),
+        )
+            .into_response(),
+        Format::Json => prepare_json_response(map, last_version_id).into_response(),
+    }
 }
 
-fn prepare_response(map: &Map) -> Json<Response> {
-    Json(Response {
+fn prepare_json_response(map: &Map, last_version_id: Option<&str>) -> Json<Response> {
+    if let Some(last_version_id) = last_version_id {
+        if let Some(changed_cells) = map.changes_since(last_version_id) {
+            return Json(Response::Delta {
+                version_id: map.version_id().to_string(),
+                changed_cells,
+            });
+        }
+    }
+
+    Json(Response::Full {
         version_id: map.version_id().to_string(),
         size: map.size(),
         colors: CellColor::ALL_COLORS
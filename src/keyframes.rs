@@ -0,0 +1,75 @@
+use crate::config::Config;
+use crate::map::Map;
+use crate::monitored_rwlock::MonitoredRwLock;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::time;
+
+/// A single full-map capture, compactly encoded via [`Map::encode_packed`] (4 bits per cell), so
+/// that thousands of them can be kept in memory for scrubbing through a timelapse.
+#[derive(Debug)]
+pub struct Keyframe {
+    pub timestamp: u64,
+    pub version_id: String,
+    pub packed: Vec<u8>,
+}
+
+/// Bounded history of [`Keyframe`]s, oldest first, evicting past `max_keyframes`
+#[derive(Debug)]
+pub struct KeyframeStore {
+    keyframes: VecDeque<Keyframe>,
+    max_keyframes: usize,
+}
+
+impl KeyframeStore {
+    pub fn new(max_keyframes: usize) -> Self {
+        Self {
+            keyframes: VecDeque::with_capacity(max_keyframes),
+            max_keyframes,
+        }
+    }
+
+    pub fn push(&mut self, keyframe: Keyframe) {
+        if self.keyframes.len() >= self.max_keyframes {
+            self.keyframes.pop_front();
+        }
+        self.keyframes.push_back(keyframe);
+    }
+
+    pub fn keyframes(&self) -> impl Iterator<Item = &Keyframe> {
+        self.keyframes.iter()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&Keyframe> {
+        self.keyframes.get(index)
+    }
+}
+
+/// Periodically capture the full [`Map`] state into `store`, so a client can later scrub through
+/// how the ecosystem evolved
+pub async fn run(config: Arc<Config>, map: Arc<MonitoredRwLock<Map>>, store: Arc<MonitoredRwLock<KeyframeStore>>) {
+    let tick_sleep = Duration::from_secs(config.snapshot_tick_seconds);
+
+    loop {
+        let keyframe = {
+            let map = map.read(module_path!());
+            Keyframe {
+                timestamp: now(),
+                version_id: map.version_id().to_string(),
+                packed: map.encode_packed(),
+            }
+        };
+
+        store.write(module_path!()).push(keyframe);
+
+        time::sleep(tick_sleep).await;
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("must be after epoch")
+        .as_secs()
+}
@@ -0,0 +1,36 @@
+use crate::keyframes::KeyframeStore;
+use crate::monitored_rwlock::MonitoredRwLock;
+use axum::extract::State;
+use axum::Json;
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Debug, Serialize)]
+pub struct Response {
+    keyframes: Vec<KeyframeMetadata>,
+}
+
+#[derive(Debug, Serialize)]
+struct KeyframeMetadata {
+    index: usize,
+    timestamp: u64,
+    version_id: String,
+}
+
+pub async fn list_keyframes(
+    State(store): State<Arc<MonitoredRwLock<KeyframeStore>>>,
+) -> Json<Response> {
+    let store = store.read(module_path!());
+
+    Json(Response {
+        keyframes: store
+            .keyframes()
+            .enumerate()
+            .map(|(index, keyframe)| KeyframeMetadata {
+                index,
+                timestamp: keyframe.timestamp,
+                version_id: keyframe.version_id.clone(),
+            })
+            .collect(),
+    })
+}
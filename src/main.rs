@@ -1,23 +1,39 @@
 mod cell;
 mod cell_color;
 mod config;
+mod direction;
 mod ecosystem;
+mod get_keyframe;
 mod get_map;
+mod keyframes;
+mod list_keyframes;
 mod map;
+mod metrics;
 mod monitored_rwlock;
 mod point;
+mod save_snapshot;
 mod set_cell_color;
+mod set_cell_colors;
+mod snapshot;
 mod web_error;
 
 use crate::config::Config;
 use crate::ecosystem::spawn_ecosystem;
+use crate::get_keyframe::get_keyframe;
 use crate::get_map::get_map;
-use crate::map::Map;
+use crate::keyframes::KeyframeStore;
+use crate::list_keyframes::list_keyframes;
+use crate::map::{Map, MapGenerationParams};
+use crate::metrics::metrics;
 use crate::monitored_rwlock::MonitoredRwLock;
+use crate::save_snapshot::save_snapshot;
 use crate::set_cell_color::set_cell_color;
+use crate::set_cell_colors::set_cell_colors;
 use anyhow::Result;
 use axum::extract::FromRef;
 use axum::{routing, Router, Server};
+use std::path::PathBuf;
+use std::sync::atomic::AtomicI32;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::time;
@@ -29,6 +45,8 @@ use tracing_subscriber::EnvFilter;
 struct State {
     map: Arc<MonitoredRwLock<Map>>,
     config: Arc<Config>,
+    keyframe_store: Arc<MonitoredRwLock<KeyframeStore>>,
+    atmosphere_water: Arc<AtomicI32>,
 }
 
 #[tokio::main]
@@ -39,21 +57,51 @@ async fn main() -> Result<()> {
         .from_env()?;
     tracing_subscriber::fmt().with_env_filter(env_filter).init();
 
-    let map = Arc::new(MonitoredRwLock::new(Map::new(&config)?));
+    let (map, restored_atmosphere_water) = match load_path() {
+        Some(path) => {
+            tracing::info!("Restoring snapshot from {:?}", path);
+            let (map, atmosphere_water) = snapshot::load(&config, &path)?;
+            (map, Some(atmosphere_water))
+        }
+        None if config.map_generate => (generate_map(&config), None),
+        None => (Map::new(&config)?, None),
+    };
+    let map = Arc::new(MonitoredRwLock::new(map));
+    let keyframe_store = Arc::new(MonitoredRwLock::new(KeyframeStore::new(
+        config.snapshot_max_keyframes,
+    )));
+
+    // Spawned before the server starts handling requests, so `WaterFlowSystem::new` recomputes
+    // its flow targets against the (possibly just-restored) heights
+    let atmosphere_water = spawn_ecosystem(config.clone(), map.clone(), restored_atmosphere_water);
 
     let state = State {
         map: map.clone(),
         config: config.clone(),
+        keyframe_store: keyframe_store.clone(),
+        atmosphere_water: atmosphere_water.clone(),
     };
 
     let serve_dir = ServeDir::new("web");
     let app = Router::new()
         .route("/api/map", routing::get(get_map))
         .route("/api/cell", routing::post(set_cell_color))
+        .route("/api/cells", routing::post(set_cell_colors))
+        .route("/api/keyframes", routing::get(list_keyframes))
+        .route("/api/keyframe", routing::get(get_keyframe))
+        .route("/api/snapshot", routing::post(save_snapshot))
+        .route("/metrics", routing::get(metrics))
         .fallback_service(serve_dir)
         .with_state(state);
 
-    spawn_ecosystem(config.clone(), map.clone());
+    tokio::spawn(keyframes::run(config.clone(), map.clone(), keyframe_store));
+
+    tokio::spawn(snapshot::run(
+        map.clone(),
+        atmosphere_water,
+        Duration::from_secs(config.world_snapshot_tick_seconds),
+        PathBuf::from(&config.world_snapshot_path),
+    ));
 
     tokio::spawn(report_lock_stats(map));
 
@@ -64,6 +112,35 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+/// Build a fresh map via [`Map::generate`] from the `map_generation_*`/`map_size`/`seed` fields of
+/// `config`, for when [`Config::map_generate`] opts out of [`Map::new`]'s height-map image.
+fn generate_map(config: &Config) -> Map {
+    let params = MapGenerationParams {
+        water_density: config.map_generation_water_density,
+        smoothing_passes: config.map_generation_smoothing_passes,
+        water_neighbor_threshold: config.map_generation_water_neighbor_threshold,
+        high_grass_distance: config.map_generation_high_grass_distance,
+        low_grass_distance: config.map_generation_low_grass_distance,
+        dry_grass_distance: config.map_generation_dry_grass_distance,
+        insect_count: config.map_generation_insect_count,
+        amphibian_count: config.map_generation_amphibian_count,
+    };
+    Map::generate(config.map_size, config.seed, &params)
+}
+
+/// Parse a `--load <path>` flag off the command line, pointing at a snapshot written by
+/// [`snapshot::save`] to restore on boot
+fn load_path() -> Option<PathBuf> {
+    let mut args = std::env::args();
+    while let Some(arg) = args.next() {
+        if arg == "--load" {
+            return args.next().map(PathBuf::from);
+        }
+    }
+
+    None
+}
+
 async fn report_lock_stats(map: Arc<MonitoredRwLock<Map>>) {
     loop {
         time::sleep(Duration::from_secs(60)).await;
@@ -73,13 +150,17 @@ async fn report_lock_stats(map: Arc<MonitoredRwLock<Map>>) {
         tracing::info!("- read wait: {:?}", stats.read_wait);
         tracing::info!("- write wait: {:?}", stats.write_wait);
 
-        if let Some(worst_reader) = stats.read_usage.into_iter().max_by_key(|&(_, value)| value) {
+        if let Some(worst_reader) = stats
+            .read_usage
+            .into_iter()
+            .max_by_key(|&(_, summary)| summary.avg)
+        {
             tracing::info!("- worst reader: {:?}", worst_reader);
         }
         if let Some(worst_writer) = stats
             .write_usage
             .into_iter()
-            .max_by_key(|&(_, value)| value)
+            .max_by_key(|&(_, summary)| summary.avg)
         {
             tracing::info!("- worst writer: {:?}", worst_writer);
         }
@@ -1,20 +1,69 @@
+use crate::cell::cell_animal::CellAnimal;
+use crate::cell::cell_grass::CellGrass;
+use crate::cell::cell_water::CellWater;
 use crate::cell::Cell;
 use crate::cell_color::CellColor;
 use crate::config::Config;
+use crate::ecosystem::amphibian::Amphibian;
+use crate::ecosystem::insect::Insect;
 use crate::point::Point;
 use anyhow::{ensure, Context, Result};
 use image::{GenericImageView, Pixel};
 use itertools::Itertools;
 use ndarray::{s, Array2};
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+use std::collections::VecDeque;
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::sync::Notify;
 
+/// How many past versions are kept in [`Map::change_log`], so that a client that last polled
+/// fewer than this many versions ago can be served a delta instead of the full map.
+const MAX_RETAINED_VERSIONS: usize = 64;
+
 #[derive(Debug)]
 pub struct Map {
     version_id: String,
     cells: Array2<Cell>,
     change_notifier: Arc<Notify>,
+    /// The color index of every cell, as of the last call to [`Self::notify_update`]. Used to
+    /// compute the diff that feeds `change_log`.
+    last_colors: Vec<usize>,
+    /// Bounded history of `(version_id, changed_cells)`, oldest first. `changed_cells` holds the
+    /// `(cell_index, new_color_index)` pairs that turned the previous version into this one.
+    change_log: VecDeque<(String, Vec<(usize, usize)>)>,
+    /// Diffusing scent trail left by prey, sampled by predators to steer towards them without an
+    /// omniscient global scan. Purely an AI signal: it never affects cell color, so updating it
+    /// does not call [`Self::notify_update`].
+    scent: Array2<f64>,
+    /// Diffusing trail left behind a [`crate::ecosystem::simple_animal::SimpleAnimal`] that just
+    /// found food, sampled by the rest of the population to bias their foraging walk towards past
+    /// finds (stigmergy) instead of wandering uniformly at random. Purely an AI signal, same as
+    /// [`Self::scent`].
+    food_pheromone: Array2<f64>,
+}
+
+/// Parameters controlling [`Map::generate`]'s procedural terrain, see that method for how each
+/// one is used
+#[derive(Debug, Clone, Copy)]
+pub struct MapGenerationParams {
+    /// Fraction of cells randomly seeded as water before the cave-automata smoothing passes
+    pub water_density: f64,
+    /// Number of smoothing passes applied to the initial random fill
+    pub smoothing_passes: usize,
+    /// Out of the 8 surrounding neighbors (out-of-bounds counted as water), the minimum number
+    /// that must be water for a cell to become, or remain, water during smoothing
+    pub water_neighbor_threshold: usize,
+    /// Cells within this BFS distance of water get `CellGrass::High`
+    pub high_grass_distance: usize,
+    /// Cells within this BFS distance of water get at least `CellGrass::Low`
+    pub low_grass_distance: usize,
+    /// Cells within this BFS distance of water get at least `CellGrass::Dry`; farther cells stay
+    /// `CellGrass::Empty`
+    pub dry_grass_distance: usize,
+    pub insect_count: usize,
+    pub amphibian_count: usize,
 }
 
 impl Map {
@@ -47,10 +96,18 @@ impl Map {
             cell.set_height(normal_height.round() as u8);
         }
 
+        let last_colors = cells.iter().map(|cell| cell.color().as_index()).collect();
+        let scent = Array2::zeros(cells.dim());
+        let food_pheromone = Array2::zeros(cells.dim());
+
         Ok(Map {
             version_id: Self::now(),
             cells,
             change_notifier: Default::default(),
+            last_colors,
+            change_log: VecDeque::with_capacity(MAX_RETAINED_VERSIONS),
+            scent,
+            food_pheromone,
         })
     }
 
@@ -79,15 +136,362 @@ impl Map {
         Ok(())
     }
 
+    /// Apply a batch of cell edits atomically: either every edit is valid and all of them are
+    /// applied (with a single [`Self::notify_update`] at the end), or none are
+    pub fn set_cell_colors(&mut self, edits: &[(Point, CellColor)]) -> Result<()> {
+        for &(point, color) in edits {
+            ensure!(self.cells.get(point).is_some(), "invalid cell position");
+            ensure!(
+                CellColor::AVAILABLE_COLORS
+                    .iter()
+                    .any(|available| available.as_index() == color.as_index()),
+                "cannot set such color"
+            );
+        }
+
+        for &(point, color) in edits {
+            self.cells[point].with_color(color)?;
+        }
+        self.notify_update();
+
+        Ok(())
+    }
+
     pub fn change_notifier(&self) -> &Arc<Notify> {
         &self.change_notifier
     }
 
+    pub fn scent(&self) -> &Array2<f64> {
+        &self.scent
+    }
+
+    /// Add `amount` to the scent at `point`, clamping out-of-bounds points to a no-op
+    pub fn deposit_scent(&mut self, point: Point, amount: f64) {
+        if let Some(scent) = self.scent.get_mut(point) {
+            *scent += amount;
+        }
+    }
+
+    /// Advance the scent field by one diffusion step: each cell's new value is an `evaporation`
+    /// fraction of a blend between its own value and the mean of its four cardinal neighbors
+    /// (missing, off-grid neighbors are simply excluded from the mean). This spreads scent
+    /// outward from its source while decaying it over time and distance.
+    pub fn diffuse_scent(&mut self, evaporation: f64, diffusion: f64) {
+        let previous = self.scent.clone();
+
+        for ((i, j), scent) in self.scent.indexed_iter_mut() {
+            let point = Point::new_ij((i, j));
+            let (neighbor_sum, neighbor_count) = Point::DIRECTIONS
+                .into_iter()
+                .filter_map(|direction| previous.get(point + direction))
+                .fold((0.0, 0usize), |(sum, count), &value| (sum + value, count + 1));
+            let neighbor_mean = if neighbor_count > 0 {
+                neighbor_sum / neighbor_count as f64
+            } else {
+                0.0
+            };
+
+            let here = previous[(i, j)];
+            *scent = evaporation * ((1.0 - diffusion) * here + diffusion * neighbor_mean);
+        }
+    }
+
+    pub fn food_pheromone(&self) -> &Array2<f64> {
+        &self.food_pheromone
+    }
+
+    /// Add `amount` to the food pheromone at `point`, clamping out-of-bounds points to a no-op
+    pub fn deposit_food_pheromone(&mut self, point: Point, amount: f64) {
+        if let Some(pheromone) = self.food_pheromone.get_mut(point) {
+            *pheromone += amount;
+        }
+    }
+
+    /// Advance the food pheromone field by one diffusion step: each cell's new value is an
+    /// `evaporation` fraction of a blend between its own value and the mean of its 8 surrounding
+    /// neighbors (missing, off-grid neighbors are simply excluded from the mean). Unlike
+    /// [`Self::diffuse_scent`], which only spreads along the 4 cardinal directions, trails need to
+    /// smear diagonally too so a forager approaching from any angle can pick one up.
+    pub fn diffuse_food_pheromone(&mut self, evaporation: f64, diffusion: f64) {
+        let previous = self.food_pheromone.clone();
+
+        for ((i, j), pheromone) in self.food_pheromone.indexed_iter_mut() {
+            let point = Point::new_ij((i, j));
+            let (neighbor_sum, neighbor_count) = Point::EIGHT_DIRECTIONS
+                .into_iter()
+                .filter_map(|direction| previous.get(point + direction))
+                .fold((0.0, 0usize), |(sum, count), &value| (sum + value, count + 1));
+            let neighbor_mean = if neighbor_count > 0 {
+                neighbor_sum / neighbor_count as f64
+            } else {
+                0.0
+            };
+
+            let here = previous[(i, j)];
+            *pheromone = evaporation * ((1.0 - diffusion) * here + diffusion * neighbor_mean);
+        }
+    }
+
     pub fn notify_update(&mut self) {
+        let changed_cells = self
+            .cells
+            .iter()
+            .map(|cell| cell.color().as_index())
+            .zip(self.last_colors.iter_mut())
+            .enumerate()
+            .filter_map(|(cell_index, (new_color, old_color))| {
+                let changed = new_color != *old_color;
+                *old_color = new_color;
+                changed.then_some((cell_index, new_color))
+            })
+            .collect();
+
         self.version_id = Self::now();
+        if self.change_log.len() >= MAX_RETAINED_VERSIONS {
+            self.change_log.pop_front();
+        }
+        self.change_log
+            .push_back((self.version_id.clone(), changed_cells));
+
         self.change_notifier.notify_waiters();
     }
 
+    /// Return the cells that changed since `version_id`, as `(cell_index, new_color_index)`
+    /// pairs, as long as that version is still inside the retained window. Returns `None` when
+    /// the version is too old (or unknown), in which case the caller should fall back to sending
+    /// the full map.
+    pub fn changes_since(&self, version_id: &str) -> Option<Vec<(usize, usize)>> {
+        let position = self
+            .change_log
+            .iter()
+            .position(|(recorded_version, _)| recorded_version == version_id)?;
+
+        Some(
+            self.change_log
+                .iter()
+                .skip(position + 1)
+                .flat_map(|(_, changed_cells)| changed_cells.iter().copied())
+                .collect(),
+        )
+    }
+
+    /// Pack every cell's color index into a `Vec<u8>`, 4 bits per cell (two cells per byte, most
+    /// significant nibble first), prefixed by the map `size` and `version_id` so the payload is
+    /// self-describing. This is ~16x smaller than the JSON encoding for the 14 colors this game
+    /// currently has.
+    pub fn encode_packed(&self) -> Vec<u8> {
+        let mut out = self.encode_header();
+
+        for mut nibbles in self
+            .cells
+            .iter()
+            .map(|cell| cell.color().as_index() as u8)
+            .chunks(2)
+            .into_iter()
+        {
+            let high = nibbles.next().unwrap_or(0);
+            let low = nibbles.next().unwrap_or(0);
+            out.push((high << 4) | low);
+        }
+
+        out
+    }
+
+    /// Run-length-encode every cell's color index as repeated `(color_index: u8, run_length: u32
+    /// little-endian)` pairs, prefixed the same way as [`Self::encode_packed`]. Large uniform
+    /// regions (grass, water) compress down to a handful of bytes.
+    pub fn encode_rle(&self) -> Vec<u8> {
+        let mut out = self.encode_header();
+
+        for (color_index, run) in &self.cells.iter().map(|cell| cell.color().as_index() as u8).group_by(|&color| color) {
+            let mut run_length = 0u32;
+            for _ in run {
+                run_length += 1;
+            }
+            out.push(color_index);
+            out.extend_from_slice(&run_length.to_le_bytes());
+        }
+
+        out
+    }
+
+    /// `size: u32` followed by the `version_id` length-prefixed as UTF-8, shared by the binary
+    /// encodings above.
+    fn encode_header(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(&(self.size() as u32).to_le_bytes());
+        let version_id = self.version_id.as_bytes();
+        out.extend_from_slice(&(version_id.len() as u16).to_le_bytes());
+        out.extend_from_slice(version_id);
+        out
+    }
+
+    /// Serialize the full grid of cells, including every animal, water and grass state and the
+    /// normalized heights, so it can be written to disk and restored after a restart. Unlike
+    /// [`Self::encode_packed`], this is not meant to be compact or stable across versions: it is
+    /// only ever read back by [`Self::from_snapshot`] running the same build.
+    pub fn to_snapshot(&self) -> Vec<u8> {
+        bincode::serialize(&self.cells).expect("cells must be serializable")
+    }
+
+    /// Rebuild a [`Map`] from a [`Self::to_snapshot`] payload. The scent and food pheromone fields
+    /// and the change log are not part of the snapshot: they start fresh, exactly like
+    /// [`Self::new`].
+    pub fn from_snapshot(config: &Config, bytes: &[u8]) -> Result<Self> {
+        let cells: Array2<Cell> = bincode::deserialize(bytes).context("invalid snapshot")?;
+        ensure!(
+            cells.dim() == (config.map_size, config.map_size),
+            "the snapshot must be {}x{}",
+            config.map_size,
+            config.map_size
+        );
+
+        let last_colors = cells.iter().map(|cell| cell.color().as_index()).collect();
+        let scent = Array2::zeros(cells.dim());
+        let food_pheromone = Array2::zeros(cells.dim());
+
+        Ok(Map {
+            version_id: Self::now(),
+            cells,
+            change_notifier: Default::default(),
+            last_colors,
+            change_log: VecDeque::with_capacity(MAX_RETAINED_VERSIONS),
+            scent,
+            food_pheromone,
+        })
+    }
+
+    /// Procedurally generate a `size`x`size` map with the "cave automata" technique: randomly
+    /// fill the grid with water at `params.water_density`, then smooth it over
+    /// `params.smoothing_passes` passes so a cell becomes (or stays) water whenever at least
+    /// `params.water_neighbor_threshold` of its 8 neighbors are water, treating out-of-bounds
+    /// neighbors as water so lakes don't bleed off the edge of the map. `height` is then derived
+    /// as each cell's BFS distance from the nearest water, grass is seeded from that same
+    /// distance (closer to water is lusher), and `params.insect_count`/`params.amphibian_count`
+    /// animals are scattered onto random dry cells. `seed` behaves like [`Config::seed`]: the
+    /// same seed and params always produce the same map.
+    pub fn generate(size: usize, seed: Option<u64>, params: &MapGenerationParams) -> Self {
+        let mut rng = match seed {
+            Some(seed) => SmallRng::seed_from_u64(seed),
+            None => SmallRng::from_entropy(),
+        };
+
+        let mut is_water =
+            Array2::from_shape_fn((size, size), |_| rng.gen_bool(params.water_density));
+        for _ in 0..params.smoothing_passes {
+            is_water = Self::smooth_cave(&is_water, params.water_neighbor_threshold);
+        }
+
+        let distance_from_water = Self::distance_from_water(&is_water);
+
+        let mut cells = Array2::from_shape_fn((size, size), |coordinates| {
+            let distance = distance_from_water[coordinates];
+            let height = distance.min(u8::MAX as usize) as u8;
+            let mut cell = Cell::empty(height);
+
+            if is_water[coordinates] {
+                cell.set_water(CellWater::Deep);
+            } else if distance <= params.high_grass_distance {
+                cell.set_grass(CellGrass::High);
+            } else if distance <= params.low_grass_distance {
+                cell.set_grass(CellGrass::Low);
+            } else if distance <= params.dry_grass_distance {
+                cell.set_grass(CellGrass::Dry);
+            }
+
+            cell
+        });
+
+        Self::scatter_animals(&mut cells, &mut rng, params);
+
+        let last_colors = cells.iter().map(|cell| cell.color().as_index()).collect();
+        let scent = Array2::zeros(cells.dim());
+        let food_pheromone = Array2::zeros(cells.dim());
+
+        Map {
+            version_id: Self::now(),
+            cells,
+            change_notifier: Default::default(),
+            last_colors,
+            change_log: VecDeque::with_capacity(MAX_RETAINED_VERSIONS),
+            scent,
+            food_pheromone,
+        }
+    }
+
+    /// One cave-automata smoothing pass: a cell becomes water if at least `neighbor_threshold`
+    /// of its 8 neighbors are water, counting any out-of-bounds neighbor as water so basins
+    /// close up instead of draining off the edge of the map
+    fn smooth_cave(is_water: &Array2<bool>, neighbor_threshold: usize) -> Array2<bool> {
+        Array2::from_shape_fn(is_water.dim(), |(i, j)| {
+            let point = Point::new_ij((i, j));
+            let water_neighbors = Point::EIGHT_DIRECTIONS
+                .into_iter()
+                .filter(|&direction| is_water.get(point + direction).copied().unwrap_or(true))
+                .count();
+
+            water_neighbors >= neighbor_threshold
+        })
+    }
+
+    /// BFS distance from the nearest water cell, used as a cheap heightfield: water itself is at
+    /// distance 0, and every other cell is the shortest number of [`Point::DIRECTIONS`] steps to
+    /// reach one
+    fn distance_from_water(is_water: &Array2<bool>) -> Array2<usize> {
+        let mut distance = Array2::from_elem(is_water.dim(), usize::MAX);
+        let mut queue = VecDeque::new();
+
+        for ((i, j), &water) in is_water.indexed_iter() {
+            if water {
+                let point = Point::new_ij((i, j));
+                distance[point] = 0;
+                queue.push_back(point);
+            }
+        }
+
+        while let Some(point) = queue.pop_front() {
+            for direction in Point::DIRECTIONS {
+                let neighbor = point + direction;
+                if distance.get(neighbor) == Some(&usize::MAX) {
+                    distance[neighbor] = distance[point] + 1;
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        distance
+    }
+
+    /// Scatter `params.insect_count`/`params.amphibian_count` animals onto random dry, empty
+    /// cells, see [`Self::place_animal`]
+    fn scatter_animals(cells: &mut Array2<Cell>, rng: &mut SmallRng, params: &MapGenerationParams) {
+        for _ in 0..params.insect_count {
+            Self::place_animal(cells, rng, || {
+                CellAnimal::Insect(Box::new(Insect::default()))
+            });
+        }
+        for _ in 0..params.amphibian_count {
+            Self::place_animal(cells, rng, || {
+                CellAnimal::Amphibian(Box::new(Amphibian::default()))
+            });
+        }
+    }
+
+    /// Drop one animal, built by `build`, onto a random dry, empty cell, retrying a candidate
+    /// that turns out to be water or already occupied so a map generated with little dry land
+    /// just ends up sparser rather than panicking
+    fn place_animal(cells: &mut Array2<Cell>, rng: &mut SmallRng, build: impl Fn() -> CellAnimal) {
+        let size = cells.nrows();
+        for _ in 0..size * size {
+            let point = Point::new(rng.gen_range(0..size), rng.gen_range(0..size));
+            let cell = &mut cells[point];
+            if cell.water().is_empty() && cell.animal().is_empty() {
+                *cell.animal_mut() = build();
+                return;
+            }
+        }
+    }
+
     /// Return exclusive references to two distinct cells.
     ///
     /// # Panics
@@ -132,3 +536,28 @@ impl Map {
             .to_string()
     }
 }
+
+/// Decode a [`Map::encode_packed`] payload back into its `version_id`, `size` and per-cell color
+/// indexes, without needing a live [`Map`]. Used to render stored keyframes.
+pub fn decode_packed(bytes: &[u8]) -> Result<(String, usize, Vec<usize>)> {
+    ensure!(bytes.len() >= 6, "payload too short");
+
+    let size = u32::from_le_bytes(bytes[0..4].try_into().unwrap()) as usize;
+    let version_id_len = u16::from_le_bytes(bytes[4..6].try_into().unwrap()) as usize;
+    let version_id_end = 6 + version_id_len;
+    ensure!(bytes.len() >= version_id_end, "payload too short");
+    let version_id = String::from_utf8(bytes[6..version_id_end].to_vec())?;
+
+    let num_cells = size * size;
+    let packed = &bytes[version_id_end..];
+    ensure!(packed.len() >= (num_cells + 1) / 2, "payload too short");
+
+    let mut cell_color_indexes = Vec::with_capacity(num_cells);
+    for i in 0..num_cells {
+        let byte = packed[i / 2];
+        let nibble = if i % 2 == 0 { byte >> 4 } else { byte & 0xf };
+        cell_color_indexes.push(nibble as usize);
+    }
+
+    Ok((version_id, size, cell_color_indexes))
+}
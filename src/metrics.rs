@@ -0,0 +1,111 @@
+use crate::cell::cell_animal::CellAnimal;
+use crate::ecosystem::snake::SnakeSpecies;
+use crate::map::Map;
+use crate::monitored_rwlock::{MonitoredRwLock, RunningAverageSummary, SummaryStats};
+use axum::extract::State;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::Arc;
+
+pub async fn metrics(State(map): State<Arc<MonitoredRwLock<Map>>>) -> String {
+    let mut out = String::new();
+
+    let stats = map.pop_stats();
+    write_lock_wait_metrics(&mut out, &stats);
+    write_lock_usage_metrics(&mut out, &stats);
+    write_population_metrics(&mut out, &map);
+
+    out
+}
+
+fn write_lock_wait_metrics(out: &mut String, stats: &SummaryStats) {
+    writeln!(
+        out,
+        "# HELP lock_read_wait_seconds Time spent waiting to acquire the map read lock"
+    )
+    .unwrap();
+    writeln!(out, "# TYPE lock_read_wait_seconds gauge").unwrap();
+    write_named_summaries(out, "lock_read_wait_seconds", &stats.read_wait_by_name);
+
+    writeln!(
+        out,
+        "# HELP lock_write_wait_seconds Time spent waiting to acquire the map write lock"
+    )
+    .unwrap();
+    writeln!(out, "# TYPE lock_write_wait_seconds gauge").unwrap();
+    write_named_summaries(out, "lock_write_wait_seconds", &stats.write_wait_by_name);
+}
+
+fn write_lock_usage_metrics(out: &mut String, stats: &SummaryStats) {
+    writeln!(
+        out,
+        "# HELP lock_read_usage_seconds Time the map read lock was held, by call site"
+    )
+    .unwrap();
+    writeln!(out, "# TYPE lock_read_usage_seconds gauge").unwrap();
+    write_named_summaries(out, "lock_read_usage_seconds", &stats.read_usage);
+
+    writeln!(
+        out,
+        "# HELP lock_write_usage_seconds Time the map write lock was held, by call site"
+    )
+    .unwrap();
+    writeln!(out, "# TYPE lock_write_usage_seconds gauge").unwrap();
+    write_named_summaries(out, "lock_write_usage_seconds", &stats.write_usage);
+}
+
+fn write_named_summaries(
+    out: &mut String,
+    metric: &str,
+    by_name: &HashMap<&'static str, RunningAverageSummary>,
+) {
+    for (&name, &summary) in by_name {
+        for (stat, value) in [
+            ("avg", summary.avg.as_secs_f64()),
+            ("min", summary.min.as_secs_f64()),
+            ("max", summary.max.as_secs_f64()),
+        ] {
+            writeln!(
+                out,
+                "{}{{name=\"{}\",stat=\"{}\"}} {}",
+                metric, name, stat, value
+            )
+            .unwrap();
+        }
+    }
+}
+
+fn write_population_metrics(out: &mut String, map: &MonitoredRwLock<Map>) {
+    let map = map.read(module_path!());
+
+    let mut insect = 0;
+    let mut amphibian = 0;
+    let mut snake_a = 0;
+    let mut snake_b = 0;
+    let mut snake_c = 0;
+
+    for cell in map.cells() {
+        match cell.animal() {
+            CellAnimal::Insect(_) => insect += 1,
+            CellAnimal::Amphibian(_) => amphibian += 1,
+            CellAnimal::Snake(snake) => match snake.species() {
+                SnakeSpecies::A => snake_a += 1,
+                SnakeSpecies::B => snake_b += 1,
+                SnakeSpecies::C => snake_c += 1,
+            },
+            CellAnimal::Empty | CellAnimal::Dead => {}
+        }
+    }
+
+    writeln!(
+        out,
+        "# HELP population Number of live animals of each species"
+    )
+    .unwrap();
+    writeln!(out, "# TYPE population gauge").unwrap();
+    writeln!(out, "population{{species=\"insect\"}} {}", insect).unwrap();
+    writeln!(out, "population{{species=\"amphibian\"}} {}", amphibian).unwrap();
+    writeln!(out, "population{{species=\"snake_a\"}} {}", snake_a).unwrap();
+    writeln!(out, "population{{species=\"snake_b\"}} {}", snake_b).unwrap();
+    writeln!(out, "population{{species=\"snake_c\"}} {}", snake_c).unwrap();
+}
@@ -35,22 +35,37 @@ struct Stats {
 
 #[derive(Debug, Default)]
 struct LockStats {
+    read_wait: RunningAverage,
+    write_wait: RunningAverage,
     read_usage: RunningAverage,
     write_usage: RunningAverage,
 }
 
 #[derive(Debug, Default)]
 pub struct SummaryStats {
-    pub read_wait: Option<Duration>,
-    pub write_wait: Option<Duration>,
-    pub read_usage: HashMap<&'static str, Duration>,
-    pub write_usage: HashMap<&'static str, Duration>,
+    pub read_wait: Option<RunningAverageSummary>,
+    pub write_wait: Option<RunningAverageSummary>,
+    pub read_wait_by_name: HashMap<&'static str, RunningAverageSummary>,
+    pub write_wait_by_name: HashMap<&'static str, RunningAverageSummary>,
+    pub read_usage: HashMap<&'static str, RunningAverageSummary>,
+    pub write_usage: HashMap<&'static str, RunningAverageSummary>,
 }
 
 #[derive(Debug, Default)]
 struct RunningAverage {
     sum: Duration,
     count: u32,
+    min: Option<Duration>,
+    max: Option<Duration>,
+}
+
+/// A snapshot of a [`RunningAverage`], taken at [`RunningAverage::pop`] time
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RunningAverageSummary {
+    pub avg: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    pub count: u32,
 }
 
 impl<T> MonitoredRwLock<T> {
@@ -65,7 +80,11 @@ impl<T> MonitoredRwLock<T> {
         let start_wait = Instant::now();
         let guard = self.inner.read().unwrap();
         let wait = start_wait.elapsed();
-        self.stats.lock().unwrap().read_wait.push(wait);
+        {
+            let mut stats = self.stats.lock().unwrap();
+            stats.read_wait.push(wait);
+            stats.named.entry(name).or_default().read_wait.push(wait);
+        }
 
         ReadGuard {
             start: Instant::now(),
@@ -79,7 +98,11 @@ impl<T> MonitoredRwLock<T> {
         let start_wait = Instant::now();
         let guard = self.inner.write().unwrap();
         let wait = start_wait.elapsed();
-        self.stats.lock().unwrap().write_wait.push(wait);
+        {
+            let mut stats = self.stats.lock().unwrap();
+            stats.write_wait.push(wait);
+            stats.named.entry(name).or_default().write_wait.push(wait);
+        }
 
         WriteGuard {
             start: Instant::now(),
@@ -91,20 +114,30 @@ impl<T> MonitoredRwLock<T> {
 
     pub fn pop_stats(&self) -> SummaryStats {
         let mut stats = self.stats.lock().unwrap();
+        let mut read_wait_by_name = HashMap::new();
+        let mut write_wait_by_name = HashMap::new();
         let mut read_usage = HashMap::new();
         let mut write_usage = HashMap::new();
         for (&name, x) in &mut stats.named {
-            if let Some(avg) = x.read_usage.pop() {
-                read_usage.insert(name, avg);
+            if let Some(summary) = x.read_wait.pop() {
+                read_wait_by_name.insert(name, summary);
+            }
+            if let Some(summary) = x.write_wait.pop() {
+                write_wait_by_name.insert(name, summary);
+            }
+            if let Some(summary) = x.read_usage.pop() {
+                read_usage.insert(name, summary);
             }
-            if let Some(avg) = x.write_usage.pop() {
-                write_usage.insert(name, avg);
+            if let Some(summary) = x.write_usage.pop() {
+                write_usage.insert(name, summary);
             }
         }
 
         SummaryStats {
             read_wait: stats.read_wait.pop(),
             write_wait: stats.write_wait.pop(),
+            read_wait_by_name,
+            write_wait_by_name,
             read_usage,
             write_usage,
         }
@@ -115,13 +148,22 @@ impl RunningAverage {
     fn push(&mut self, sample: Duration) {
         self.sum += sample;
         self.count += 1;
+        self.min = Some(self.min.map_or(sample, |min| min.min(sample)));
+        self.max = Some(self.max.map_or(sample, |max| max.max(sample)));
     }
 
-    fn pop(&mut self) -> Option<Duration> {
-        let avg = (self.count > 0).then(|| self.sum / self.count);
-        self.count = 0;
+    fn pop(&mut self) -> Option<RunningAverageSummary> {
+        let summary = (self.count > 0).then(|| RunningAverageSummary {
+            avg: self.sum / self.count,
+            min: self.min.unwrap_or_default(),
+            max: self.max.unwrap_or_default(),
+            count: self.count,
+        });
         self.sum = Duration::ZERO;
-        avg
+        self.count = 0;
+        self.min = None;
+        self.max = None;
+        summary
     }
 }
 
@@ -1,9 +1,11 @@
 use ndarray::{Ix2, NdIndex};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::fmt::Debug;
 use std::ops::{Add, Mul, Neg, Range, Sub};
 
 /// Defines a point that may or may not be inside the map space
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
 pub struct Point {
     pub x: isize,
     pub y: isize,
@@ -29,15 +31,42 @@ pub struct CircumferenceIter {
     next_point: Option<Point>,
 }
 
+/// Iterates over a filled disk, measured in euclidean distance, instead of the taxicab diamond
+/// that [`CircleIter`] produces
+#[derive(Debug, Clone)]
+pub struct EuclideanCircleIter {
+    center: Point,
+    radius: isize,
+    map_size: isize,
+    current_dy: isize,
+    current_x_range: Range<isize>,
+}
+
+/// Iterates over the outline of a disk, measured in euclidean distance, instead of the taxicab
+/// diamond that [`CircumferenceIter`] produces
+#[derive(Debug, Clone)]
+pub struct EuclideanCircumferenceIter {
+    points: std::collections::hash_set::IntoIter<Point>,
+}
+
 impl Point {
-    pub const X: Point = Point { x: 1, y: 0 };
-    pub const Y: Point = Point { x: 0, y: 1 };
     pub const DIRECTIONS: [Point; 4] = [
         Point { x: 1, y: 0 },
         Point { x: 0, y: 1 },
         Point { x: -1, y: 0 },
         Point { x: 0, y: -1 },
     ];
+    /// Like [`Self::DIRECTIONS`], but also including the 4 diagonals
+    pub const EIGHT_DIRECTIONS: [Point; 8] = [
+        Point { x: 1, y: 0 },
+        Point { x: 1, y: 1 },
+        Point { x: 0, y: 1 },
+        Point { x: -1, y: 1 },
+        Point { x: -1, y: 0 },
+        Point { x: -1, y: -1 },
+        Point { x: 0, y: -1 },
+        Point { x: 1, y: -1 },
+    ];
 
     pub fn new<X, Y>(x: X, y: Y) -> Self
     where
@@ -107,33 +136,75 @@ impl Point {
         }
     }
 
-    pub fn distance(self, another: Self) -> usize {
-        let delta_x = self.x.abs_diff(another.x);
-        let delta_y = self.y.abs_diff(another.y);
-        delta_x + delta_y
-    }
+    /// Iterate over all valid points inside euclidean distance `radius` from this point, i.e. a
+    /// round filled disk rather than the taxicab diamond that [`Self::circle`] produces.
+    ///
+    /// The points are not returned in any specific order.
+    pub fn euclidean_circle(self, radius: usize, map_size: usize) -> EuclideanCircleIter {
+        let radius = radius as isize;
+        let map_size = map_size as isize;
 
-    pub fn turn_right(self) -> Self {
-        Point {
-            x: -self.y,
-            y: self.x,
+        EuclideanCircleIter {
+            center: self,
+            radius,
+            map_size,
+            current_dy: -radius - 1,
+            current_x_range: 0..0,
         }
     }
 
-    pub fn turn_left(self) -> Self {
-        Point {
-            x: self.y,
-            y: -self.x,
+    /// Iterate over all valid points at euclidean distance `radius` from this point, i.e. a round
+    /// outline rather than the taxicab diamond that [`Self::circumference`] produces.
+    ///
+    /// Uses the midpoint circle algorithm. The points are not returned in any specific order.
+    pub fn euclidean_circumference(self, radius: usize, map_size: usize) -> EuclideanCircumferenceIter {
+        let r = radius as isize;
+        let mut points = HashSet::new();
+        let mut push = |x: isize, y: isize| {
+            let point = Point::new(self.x + x, self.y + y);
+            if point.is_valid(map_size) {
+                points.insert(point);
+            }
+        };
+
+        if r == 0 {
+            push(0, 0);
+        } else {
+            let mut x = r;
+            let mut y = 0isize;
+            let mut d = 1 - r;
+
+            while x >= y {
+                push(x, y);
+                push(-x, y);
+                push(x, -y);
+                push(-x, -y);
+                push(y, x);
+                push(-y, x);
+                push(y, -x);
+                push(-y, -x);
+
+                y += 1;
+                if d < 0 {
+                    d += 2 * y + 1;
+                } else {
+                    x -= 1;
+                    d += 2 * (y - x) + 1;
+                }
+            }
         }
-    }
 
-    pub fn turn_over(self) -> Self {
-        Point {
-            x: -self.x,
-            y: -self.y,
+        EuclideanCircumferenceIter {
+            points: points.into_iter(),
         }
     }
 
+    pub fn distance(self, another: Self) -> usize {
+        let delta_x = self.x.abs_diff(another.x);
+        let delta_y = self.y.abs_diff(another.y);
+        delta_x + delta_y
+    }
+
     fn valid_range(center: isize, radius: isize, map_size: isize) -> Range<isize> {
         let start = (center - radius).max(0);
         let end = (center + radius + 1).min(map_size);
@@ -145,6 +216,21 @@ impl Point {
     }
 }
 
+/// Integer square root, rounded down, of a non-negative number (negative inputs return 0)
+fn isqrt(n: isize) -> isize {
+    if n <= 0 {
+        return 0;
+    }
+
+    let mut x = n;
+    let mut y = (x + 1) / 2;
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+    x
+}
+
 unsafe impl NdIndex<Ix2> for Point {
     fn index_checked(&self, dim: &Ix2, strides: &Ix2) -> Option<isize> {
         let i = usize::try_from(self.y).ok()?;
@@ -257,6 +343,38 @@ impl Iterator for CircumferenceIter {
     }
 }
 
+impl Iterator for EuclideanCircleIter {
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(x) = self.current_x_range.next() {
+                return Some(Point::new(x, self.center.y + self.current_dy));
+            }
+
+            self.current_dy += 1;
+            if self.current_dy > self.radius {
+                return None;
+            }
+
+            if !Point::is_in_valid_range(self.center.y + self.current_dy, self.map_size) {
+                continue;
+            }
+
+            let dx_max = isqrt(self.radius * self.radius - self.current_dy * self.current_dy);
+            self.current_x_range = Point::valid_range(self.center.x, dx_max, self.map_size);
+        }
+    }
+}
+
+impl Iterator for EuclideanCircumferenceIter {
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.points.next()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -448,4 +566,87 @@ mod tests {
             &[[97, 20], [98, 21], [98, 19], [99, 22], [99, 18]],
         );
     }
+
+    #[test]
+    fn euclidean_circle() {
+        let map_size = 100;
+
+        let check = |center: Point, radius: usize, expected: &[[isize; 2]]| {
+            let points: HashSet<_> = center
+                .euclidean_circle(radius, map_size)
+                .map(|point| [point.x, point.y])
+                .collect();
+
+            let expected: HashSet<_> = expected.iter().copied().collect();
+            assert_eq!(points, expected);
+        };
+
+        check(Point::new(10, 20), 0, &[[10, 20]]);
+        check(
+            Point::new(10, 20),
+            1,
+            &[[9, 20], [10, 19], [10, 20], [10, 21], [11, 20]],
+        );
+        check(
+            Point::new(0, 20),
+            1,
+            &[[0, 19], [0, 20], [0, 21], [1, 20]],
+        );
+    }
+
+    #[test]
+    fn euclidean_circumference() {
+        let map_size = 100;
+
+        let check = |center: Point, radius: usize, expected: &[[isize; 2]]| {
+            let points: HashSet<_> = center
+                .euclidean_circumference(radius, map_size)
+                .map(|point| [point.x, point.y])
+                .collect();
+
+            let expected: HashSet<_> = expected.iter().copied().collect();
+            assert_eq!(points, expected);
+        };
+
+        check(Point::new(10, 20), 0, &[[10, 20]]);
+        check(
+            Point::new(10, 20),
+            1,
+            &[[9, 20], [11, 20], [10, 19], [10, 21]],
+        );
+        check(
+            Point::new(10, 20),
+            5,
+            &[
+                [5, 18],
+                [5, 19],
+                [5, 20],
+                [5, 21],
+                [5, 22],
+                [6, 17],
+                [6, 23],
+                [7, 16],
+                [7, 24],
+                [8, 15],
+                [8, 25],
+                [9, 15],
+                [9, 25],
+                [10, 15],
+                [10, 25],
+                [11, 15],
+                [11, 25],
+                [12, 15],
+                [12, 25],
+                [13, 16],
+                [13, 24],
+                [14, 17],
+                [14, 23],
+                [15, 18],
+                [15, 19],
+                [15, 20],
+                [15, 21],
+                [15, 22],
+            ],
+        );
+    }
 }
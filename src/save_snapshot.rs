@@ -0,0 +1,35 @@
+use crate::config::Config;
+use crate::map::Map;
+use crate::monitored_rwlock::MonitoredRwLock;
+use crate::snapshot;
+use crate::web_error::WebError;
+use axum::extract::State;
+use axum::Json;
+use serde::Serialize;
+use std::path::Path;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::Arc;
+
+#[derive(Debug, Serialize)]
+pub struct Response {
+    version_id: String,
+}
+
+/// Trigger an immediate write of the full simulation state, on top of the periodic one started in
+/// `main`
+pub async fn save_snapshot(
+    State(map): State<Arc<MonitoredRwLock<Map>>>,
+    State(atmosphere_water): State<Arc<AtomicI32>>,
+    State(config): State<Arc<Config>>,
+) -> Result<Json<Response>, WebError> {
+    let map = map.read(module_path!());
+    snapshot::save(
+        &map,
+        atmosphere_water.load(Ordering::Relaxed),
+        Path::new(&config.world_snapshot_path),
+    )?;
+
+    Ok(Json(Response {
+        version_id: map.version_id().to_string(),
+    }))
+}
@@ -0,0 +1,86 @@
+use crate::cell_color::CellColor;
+use crate::map::Map;
+use crate::monitored_rwlock::MonitoredRwLock;
+use crate::point::Point;
+use crate::web_error::WebError;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response as AxumResponse};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Debug, Deserialize)]
+pub struct Edit {
+    x_index: usize,
+    y_index: usize,
+    color_index: usize,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Request {
+    /// The `version_id` the client last saw the map at. The batch is only applied if the map is
+    /// still at this version, so two users painting concurrently can't silently clobber each
+    /// other.
+    last_version_id: String,
+    edits: Vec<Edit>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum Response {
+    Ok {
+        version_id: String,
+    },
+    /// The map moved on since `last_version_id`. `changed_cells` lists what changed in the
+    /// meantime (when still inside the retained window) so the client can rebase and retry
+    /// instead of re-fetching the whole map.
+    Conflict {
+        version_id: String,
+        changed_cells: Vec<(usize, usize)>,
+    },
+}
+
+impl IntoResponse for Response {
+    fn into_response(self) -> AxumResponse {
+        let status = match &self {
+            Response::Ok { .. } => StatusCode::OK,
+            Response::Conflict { .. } => StatusCode::CONFLICT,
+        };
+
+        (status, Json(self)).into_response()
+    }
+}
+
+pub async fn set_cell_colors(
+    State(map): State<Arc<MonitoredRwLock<Map>>>,
+    Json(request): Json<Request>,
+) -> Result<Response, WebError> {
+    let mut map = map.write(module_path!());
+
+    if map.version_id() != request.last_version_id {
+        return Ok(Response::Conflict {
+            changed_cells: map
+                .changes_since(&request.last_version_id)
+                .unwrap_or_default(),
+            version_id: map.version_id().to_string(),
+        });
+    }
+
+    let edits = request
+        .edits
+        .iter()
+        .map(|edit| {
+            let point = Point::new(edit.x_index, edit.y_index);
+            let color = CellColor::try_from_index(edit.color_index)?;
+            Ok((point, color))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()
+        .map_err(WebError::bad_request)?;
+
+    map.set_cell_colors(&edits).map_err(WebError::bad_request)?;
+
+    Ok(Response::Ok {
+        version_id: map.version_id().to_string(),
+    })
+}
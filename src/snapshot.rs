@@ -0,0 +1,67 @@
+use crate::config::Config;
+use crate::map::Map;
+use crate::monitored_rwlock::MonitoredRwLock;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time;
+
+/// On-disk capture of the whole simulation: [`Map::to_snapshot`]'s bytes plus the water cycle's
+/// atmosphere budget. The scent and food pheromone fields and the change log are not saved and
+/// start fresh on restore, see [`Map::from_snapshot`]
+#[derive(Debug, Deserialize, Serialize)]
+struct SimulationSnapshot {
+    map: Vec<u8>,
+    atmosphere_water: i32,
+}
+
+/// Write the current state of `map` and `atmosphere_water` to `path`
+pub fn save(map: &Map, atmosphere_water: i32, path: &Path) -> Result<()> {
+    let snapshot = SimulationSnapshot {
+        map: map.to_snapshot(),
+        atmosphere_water,
+    };
+    let bytes = bincode::serialize(&snapshot).context("encoding snapshot")?;
+    std::fs::write(path, bytes).with_context(|| format!("writing snapshot to {path:?}"))?;
+
+    Ok(())
+}
+
+/// Restore a [`Map`] and the atmosphere water budget previously written by [`save`].
+///
+/// The caller still has to re-derive everything else that depends on the map, notably
+/// [`crate::ecosystem::water_flow::WaterFlowSystem`]'s flow targets, since those are computed
+/// from the heights rather than stored
+pub fn load(config: &Config, path: &Path) -> Result<(Map, i32)> {
+    let bytes = std::fs::read(path).with_context(|| format!("reading snapshot from {path:?}"))?;
+    let snapshot: SimulationSnapshot = bincode::deserialize(&bytes).context("decoding snapshot")?;
+    let map = Map::from_snapshot(config, &snapshot.map)?;
+
+    Ok((map, snapshot.atmosphere_water))
+}
+
+/// Periodically write the current simulation state to `path`, so a restart can resume close to
+/// where the simulation left off instead of regenerating a fresh world
+pub async fn run(
+    map: Arc<MonitoredRwLock<Map>>,
+    atmosphere_water: Arc<AtomicI32>,
+    tick_sleep: Duration,
+    path: std::path::PathBuf,
+) {
+    loop {
+        time::sleep(tick_sleep).await;
+
+        let result = {
+            let map = map.read(module_path!());
+            save(&map, atmosphere_water.load(Ordering::Relaxed), &path)
+        };
+        if let Err(error) = result {
+            tracing::error!("Failed to write snapshot: {:#}", error);
+        } else {
+            tracing::info!("Wrote snapshot to {:?}", path);
+        }
+    }
+}